@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use design::Design;
+
+// How far from a whole number a building count may be and still count as
+// whole, so that rounding in the solution does not add a machine.
+const WHOLE_TOLERANCE : f32 = 0.01;
+
+// The solved operating rates of a design.
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    // Results for each building, in the same order as the buildings of the
+    // design.
+    pub buildings: Vec<BuildingAnalysis>,
+    // How much of each resource line is netted per second, keyed by line
+    // name. Lines that are consumed have negative rates.
+    pub line_rates: HashMap<String, f32>,
+    // Electric energy used by all buildings and their beacons, in kW.
+    pub total_energy: f32,
+    // Electric energy put out by all generators, in kW.
+    pub power_production: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct BuildingAnalysis {
+    // How many copies of the building are operating.
+    pub count: f32,
+    // Electric energy used by all copies of the building, in kW. Burner
+    // buildings show up as fuel inputs instead.
+    pub energy: f32,
+    // Energy used by the beacons of all copies of the building, in kW.
+    pub beacon_energy: f32,
+    // Accumulators needed by all copies of the building.
+    pub accumulators: f32,
+    // Pollution given off by all copies of the building, per minute.
+    pub pollution: f32,
+    // Rates per second at which all copies together consume from and
+    // produce into each of their lines.
+    pub inputs: Vec<(String, f32)>,
+    pub outputs: Vec<(String, f32)>,
+}
+
+impl Analysis {
+    pub(crate) fn from_solution(design: &Design, solution: &[f32]) -> Analysis {
+        let mut buildings = Vec::new();
+        let mut total_energy = 0.0;
+        let mut power_production = 0.0;
+        for building in design.buildings.iter() {
+            let count = solution[building.index];
+            let modified_recipe = building.modified_recipe();
+            let inputs = modified_recipe.inputs.iter()
+                .map(|&(ref line, qty)| (line.name.clone(), qty * count / modified_recipe.time))
+                .collect();
+            power_production += modified_recipe.outputs.iter()
+                .filter(|&(line, _)| line.resource_type.is_power_grid())
+                .map(|&(_, qty)| qty * count / modified_recipe.time)
+                .sum::<f32>();
+            let outputs = modified_recipe.outputs.iter()
+                .map(|&(ref line, qty)| (line.name.clone(), qty * count / modified_recipe.time))
+                .collect();
+            let building_analysis = BuildingAnalysis {
+                count,
+                energy: building.electric_energy_consumption() * count,
+                beacon_energy: building.beacon_energy_consumption() * count,
+                accumulators: building.accumulators * count,
+                pollution: building.pollution_rate() * count,
+                inputs,
+                outputs,
+            };
+            total_energy += building_analysis.energy + building_analysis.beacon_energy;
+            buildings.push(building_analysis);
+        }
+        let line_rates = design.resource_lines.values()
+            .map(|line| (line.name.clone(), solution[line.index]))
+            .collect();
+        Analysis {
+            buildings,
+            line_rates,
+            total_energy,
+            power_production,
+        }
+    }
+}
+
+impl Analysis {
+    // The smallest multiplier of the design, up to `max_multiplier`, that
+    // makes every building count a whole number.
+    pub fn whole_multiplier(&self, max_multiplier: u32) -> Option<u32> {
+        (1..max_multiplier + 1).find(|&multiplier| {
+            self.buildings.iter().all(|building| {
+                let count = building.count * multiplier as f32;
+                (count - count.round()).abs() <= WHOLE_TOLERANCE
+            })
+        })
+    }
+}
+
+impl BuildingAnalysis {
+    // The number of machines to place, rounding the count up.
+    pub fn whole_count(&self) -> f32 {
+        (self.count - WHOLE_TOLERANCE).ceil().max(0.0)
+    }
+
+    // The fraction of the time the placed machines are busy.
+    pub fn utilization(&self) -> f32 {
+        let whole_count = self.whole_count();
+        if whole_count == 0.0 {
+            0.0
+        } else {
+            (self.count / whole_count).min(1.0)
+        }
+    }
+}
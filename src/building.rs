@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use database::Database;
+use design::ResourceLine;
+use modules::{Modifiers, Module};
+use recipe::{ProtoRecipe, Recipe, RecipeCategory};
+
+#[derive(Debug, Clone)]
+pub struct Building {
+    pub name: &'static str,
+    pub recipe: Recipe,
+    pub energy_consumption: f32,
+    pub drain: f32,
+    pub crafting_speed: f32,
+    pub modules: Vec<(Module, i16)>,
+    pub module_slots: i16,
+    pub beacons: Option<Beacons>,
+    pub energy_source: EnergySource,
+    // The line burner buildings take their fuel from.
+    pub fuel: Option<ResourceLine>,
+    // Productivity from research, on top of that from modules.
+    pub base_productivity: f32,
+    // Extra heat output of nuclear reactors from their active neighbors, as
+    // a fraction of their base output.
+    pub neighbor_bonus: f32,
+    // How many accumulators each copy of the building needs.
+    pub accumulators: f32,
+    // Pollution per minute at base energy use.
+    pub pollution: f32,
+    // A count the design fixes the building at, such as buildings that are
+    // already placed, or limits its count must fall within.
+    pub count: Option<f32>,
+    pub minimum_count: Option<f32>,
+    pub maximum_count: Option<f32>,
+    // Modules the optimizer may fill the building's slots with, instead of
+    // the modules given.
+    pub allowed_modules: Vec<Module>,
+    pub(crate) index: usize,
+}
+
+// Burner buildings turn the energy value of their fuel into work, with some
+// loss given by their efficiency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EnergySource {
+    Electric,
+    Burner { efficiency: f32 },
+}
+
+// Beacons affecting a single building. Every beacon in range transmits the
+// effects of its modules at half strength.
+#[derive(Debug, Clone)]
+pub struct Beacons {
+    // How many beacons each copy of the building is in range of.
+    pub count: i16,
+    pub modules: Vec<(Module, i16)>,
+    // How many copies of the building each beacon reaches. Beacons between
+    // rows of machines are shared, so there are fewer beacons to power than
+    // the count suggests.
+    pub shared_by: f32,
+}
+
+const BEACON_EFFECTIVITY : f32 = 0.5;
+const BEACON_ENERGY_CONSUMPTION : f32 = 480.0;
+pub const BEACON_MODULE_SLOTS : i16 = 2;
+// Modules can slow a building down, or cut its energy use, to no less than
+// 20% of normal.
+const MINIMUM_SPEED : f32 = 0.2;
+const MINIMUM_ENERGY : f32 = 0.2;
+
+impl Building {
+    pub(crate) fn analysis_coefficients(&self) -> HashMap<usize, f32> {
+        let mut coefficients = HashMap::new();
+        let modified_recipe = self.modified_recipe();
+        // Inputs have positive coefficients and outputs have negative coefficients.
+        // This is so that the sign of the resource line is positive for outputs and
+        // negative for inputs (since we set the diagonal coefficient to be 1).
+        for &(ref line, qty) in modified_recipe.inputs.iter() {
+            match coefficients.entry(line.index) {
+                Entry::Occupied(mut ent) => {
+                    let coeff : &mut f32 = ent.get_mut();
+                    *coeff += qty / modified_recipe.time;
+                },
+                Entry::Vacant(ent) => {
+                    ent.insert(qty / modified_recipe.time);
+                },
+            }
+        }
+        for &(ref line, qty) in modified_recipe.outputs.iter() {
+            match coefficients.entry(line.index) {
+                Entry::Occupied(mut ent) => {
+                    let coeff : &mut f32 = ent.get_mut();
+                    *coeff += -qty / modified_recipe.time;
+                },
+                Entry::Vacant(ent) => {
+                    ent.insert(-qty / modified_recipe.time);
+                },
+            }
+        }
+        coefficients
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        let mut effects : Vec<(Module, f32)> = self.modules.iter()
+            .map(|&(module, count)| (module, f32::from(count)))
+            .collect();
+        if let Some(ref beacons) = self.beacons {
+            for &(module, count) in beacons.modules.iter() {
+                let strength = BEACON_EFFECTIVITY * f32::from(beacons.count) * f32::from(count);
+                effects.push((module, strength));
+            }
+        }
+        Modifiers::from_modules(&effects)
+    }
+
+    pub fn modified_recipe(&self) -> Recipe {
+        let modifiers = self.modifiers();
+        let mut modified_outputs = Vec::new();
+        for &(ref line, amount) in self.recipe.outputs.iter() {
+            let mut modified_amount = amount * (1.0 + self.base_productivity + modifiers.productivity);
+            if line.resource_type.is_energy() {
+                modified_amount *= 1.0 + self.neighbor_bonus;
+            }
+            modified_outputs.push((line.clone(), modified_amount));
+        }
+        let modified_crafting_speed = self.crafting_speed * (1.0 + modifiers.speed).max(MINIMUM_SPEED);
+        let modified_time = self.recipe.time / modified_crafting_speed;
+        // Fuel is burned for as long as the recipe runs.
+        let mut modified_inputs = self.recipe.inputs.clone();
+        if let Some(fuel_rate) = self.fuel_rate() {
+            let fuel_line = self.fuel.clone().unwrap();
+            modified_inputs.push((fuel_line, fuel_rate * modified_time));
+        }
+        Recipe {
+            name: self.recipe.name,
+            inputs: modified_inputs,
+            outputs: modified_outputs,
+            time: modified_time,
+            category: self.recipe.category,
+        }
+    }
+
+    pub fn modified_energy_consumption(&self) -> f32 {
+        let modifiers = self.modifiers();
+        self.energy_consumption * modifiers.energy.max(MINIMUM_ENERGY) + self.drain
+    }
+
+    // Electric energy used by the building, in kW. Burner buildings use
+    // none; their energy is accounted for by their fuel.
+    pub fn electric_energy_consumption(&self) -> f32 {
+        match self.energy_source {
+            EnergySource::Electric => self.modified_energy_consumption(),
+            EnergySource::Burner { .. } => 0.0,
+        }
+    }
+
+    // How many items of fuel per second a burner building goes through.
+    pub fn fuel_rate(&self) -> Option<f32> {
+        match (self.energy_source, &self.fuel) {
+            (EnergySource::Burner { efficiency }, Some(fuel_line)) => {
+                let fuel_value = fuel_line.resource_type.fuel_value()?;
+                Some(self.modified_energy_consumption() / (fuel_value * efficiency))
+            },
+            _ => None,
+        }
+    }
+
+    // Pollution given off per minute, which scales with the energy use
+    // modules add or save (but not with the drain).
+    pub fn pollution_rate(&self) -> f32 {
+        self.pollution * self.modifiers().energy.max(MINIMUM_ENERGY)
+    }
+
+    // Each copy of the building is charged for its share of the beacons in
+    // range of it, so that shared beacons are only powered once.
+    pub fn beacon_energy_consumption(&self) -> f32 {
+        match self.beacons {
+            Some(ref beacons) => f32::from(beacons.count) / beacons.shared_by * BEACON_ENERGY_CONSUMPTION,
+            None => 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProtoBuilding {
+    pub name: &'static str,
+    pub energy_consumption: f32,
+    pub drain: f32,
+    pub crafting_speed: f32,
+    pub module_slots: i16,
+    // Only used by mining drills, where the difference between the mining
+    // power and the hardness of the resource scales the mining speed.
+    pub mining_power: f32,
+    pub energy_source: EnergySource,
+    // The kinds of recipes the building can run.
+    pub categories: Vec<RecipeCategory>,
+    // Extra heat per active neighbor for nuclear reactors.
+    pub neighbor_bonus: f32,
+    // Accumulators needed to carry a solar panel through the night.
+    pub accumulators: f32,
+    // Pollution given off per minute when running at base energy use.
+    pub pollution: f32,
+}
+
+impl ProtoBuilding {
+    pub fn from_name(name: &str) -> Option<ProtoBuilding> {
+        Database::current().find_building(name).cloned()
+    }
+
+    // The average neighbor bonus of reactors laid out in a grid, where each
+    // reactor gets its bonus once for every adjacent reactor.
+    pub fn layout_neighbor_bonus(&self, rows: u32, columns: u32) -> f32 {
+        let adjacent_pairs = rows * (columns - 1) + columns * (rows - 1);
+        self.neighbor_bonus * 2.0 * adjacent_pairs as f32 / (rows * columns) as f32
+    }
+
+    pub fn accepts(&self, category: RecipeCategory) -> bool {
+        self.categories.contains(&category)
+    }
+
+    // The crafting speed of this building when running the given recipe.
+    pub fn crafting_speed_for(&self, proto_recipe: &ProtoRecipe) -> f32 {
+        if self.mining_power > 0.0 {
+            let hardness = proto_recipe.outputs.iter()
+                .map(|&(resource, _)| resource.mining_hardness())
+                .fold(0.0, f32::max);
+            self.crafting_speed * (self.mining_power - hardness)
+        } else {
+            self.crafting_speed
+        }
+    }
+}
@@ -0,0 +1,516 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+
+use building::{EnergySource, ProtoBuilding};
+use error::InputError;
+use parse::{clean, Data};
+use recipe::{ProtoRecipe, RecipeCategory, RecipeCost};
+use resource::{ProtoResource, Resource};
+
+// The game data used when no other is loaded, in the same format as the
+// files read by `Database::from_file`.
+static DEFAULT_DATA : &str = include_str!("../data/base.txt");
+
+lazy_static! {
+    static ref CURRENT : RwLock<Arc<Database>> = {
+        let database = Database::parse(DEFAULT_DATA.as_bytes())
+            .unwrap_or_else(|e| panic!("Invalid default data:\n{}", e));
+        RwLock::new(Arc::new(database))
+    };
+    static ref INTERNED : Mutex<HashSet<&'static str>> = Mutex::new(HashSet::new());
+}
+
+thread_local! {
+    // A database made current on this thread by `Database::activate`, which
+    // takes the place of the installed one.
+    static ACTIVE : RefCell<Option<Arc<Database>>> = const { RefCell::new(None) };
+}
+
+// Names from the data are kept for the life of the program, so that
+// resources, buildings and recipes can refer to them cheaply.
+pub(crate) fn intern(name: &str) -> &'static str {
+    let mut interned = INTERNED.lock().unwrap();
+    if let Some(&existing) = interned.get(name) {
+        return existing;
+    }
+    let leaked : &'static str = Box::leak(name.to_string().into_boxed_str());
+    interned.insert(leaked);
+    leaked
+}
+
+// Keeps a database current on this thread while it is alive.
+pub(crate) struct ActiveDatabase {
+    previous: Option<Arc<Database>>,
+}
+
+impl Drop for ActiveDatabase {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        ACTIVE.with(|active| *active.borrow_mut() = previous);
+    }
+}
+
+// The resources, recipe categories, buildings and recipes of the game.
+#[derive(Debug, Clone)]
+pub struct Database {
+    pub(crate) resources: Vec<ProtoResource>,
+    pub(crate) categories: Vec<RecipeCategory>,
+    pub(crate) buildings: Vec<ProtoBuilding>,
+    pub(crate) recipes: Vec<ProtoRecipe>,
+    pub(crate) default_fuel: Option<Resource>,
+}
+
+impl Database {
+    // The database that resources, buildings and recipes are looked up in:
+    // the one activated on this thread, or else the installed one.
+    pub fn current() -> Arc<Database> {
+        ACTIVE.with(|active| active.borrow().clone())
+            .unwrap_or_else(|| CURRENT.read().unwrap().clone())
+    }
+
+    // Makes this the database designs are read against by default. Designs
+    // take what they need from the data while they are read, so those read
+    // before the switch are unaffected.
+    pub fn install(self) {
+        *CURRENT.write().unwrap() = Arc::new(self);
+    }
+
+    // Makes `database` current on this thread until the returned guard is
+    // dropped, for reading a design against it. Nothing is looked up once
+    // the design is read: resources carry their properties and buildings
+    // their recipes, so designs read against different databases can be used
+    // side by side.
+    pub(crate) fn activate(database: &Arc<Database>) -> ActiveDatabase {
+        let previous = ACTIVE.with(|active| active.replace(Some(database.clone())));
+        ActiveDatabase {
+            previous,
+        }
+    }
+
+    pub fn parse(contents: &[u8]) -> Result<Database, InputError> {
+        let cleaned = clean(contents);
+        Data::from_cleaned(&cleaned)
+            .and_then(Database::from_data)
+            .map_err(|e| e.in_source(contents))
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Database, InputError> {
+        let path = path.as_ref();
+        let mut contents = Vec::new();
+        File::open(path)
+            .and_then(|mut input| input.read_to_end(&mut contents))
+            .map_err(|e| InputError::new(&format!("Unable to read {}: {}", path.display(), e)))?;
+        Database::parse(&contents).map_err(|e| e.in_file(&path.display().to_string()))
+    }
+
+    pub fn resources(&self) -> &[ProtoResource] {
+        &self.resources
+    }
+
+    pub fn categories(&self) -> &[RecipeCategory] {
+        &self.categories
+    }
+
+    pub fn buildings(&self) -> &[ProtoBuilding] {
+        &self.buildings
+    }
+
+    pub fn recipes(&self) -> &[ProtoRecipe] {
+        &self.recipes
+    }
+
+    // The fuel burner buildings are given when a design is planned.
+    pub fn default_fuel(&self) -> Option<Resource> {
+        self.default_fuel
+    }
+
+    pub fn resource(&self, resource: Resource) -> Option<&ProtoResource> {
+        self.resources.iter().find(|proto| proto.resource == resource)
+    }
+
+    pub fn find_resource(&self, name: &str) -> Option<Resource> {
+        let name = name.to_lowercase();
+        self.resources.iter()
+            .find(|proto| proto.resource.name().to_lowercase() == name
+                  || proto.aliases.iter().any(|alias| alias.to_lowercase() == name))
+            .map(|proto| proto.resource)
+    }
+
+    pub fn find_category(&self, name: &str) -> Option<RecipeCategory> {
+        let name = name.to_lowercase();
+        self.categories.iter().find(|category| category.name.to_lowercase() == name).cloned()
+    }
+
+    pub fn find_building(&self, name: &str) -> Option<&ProtoBuilding> {
+        let name = name.to_lowercase();
+        self.buildings.iter().find(|proto| proto.name.to_lowercase() == name)
+    }
+
+    pub fn find_recipe(&self, name: &str) -> Option<&ProtoRecipe> {
+        let name = name.to_lowercase();
+        self.recipes.iter()
+            .find(|proto| proto.name.to_lowercase() == name
+                  || proto.aliases.iter().any(|alias| alias.to_lowercase() == name))
+    }
+
+    fn from_data(data: Vec<Data>) -> Result<Database, InputError> {
+        let mut database = Database {
+            resources: Vec::new(),
+            categories: Vec::new(),
+            buildings: Vec::new(),
+            recipes: Vec::new(),
+            default_fuel: None,
+        };
+        let mut sections : HashMap<&str, &Data> = HashMap::new();
+        for datum in data.iter() {
+            match datum.value {
+                "Resources" | "Categories" | "Buildings" | "Recipes" | "Default Fuel" => {
+                    if sections.insert(datum.value, datum).is_some() {
+                        return Err(InputError::new(&format!("Duplicate section: {}", datum.value)).at(datum));
+                    }
+                },
+                _ => return Err(InputError::new(&format!("Unknown section: {}", datum.value)).at(datum)),
+            }
+        }
+
+        // Later sections refer to the names defined by earlier ones.
+        if let Some(section) = sections.get("Resources") {
+            for resource_datum in section.children.iter() {
+                let proto = parse_resource(resource_datum)?;
+                if database.find_resource(proto.resource.name()).is_some() {
+                    return Err(InputError::new(&format!("Duplicate resource: {}", resource_datum.value)).at(resource_datum));
+                }
+                database.resources.push(proto);
+            }
+        }
+        let mut default_buildings = Vec::new();
+        if let Some(section) = sections.get("Categories") {
+            for category_datum in section.children.iter() {
+                let mut default_building = None;
+                let mut mining = false;
+                for property_datum in category_datum.children.iter() {
+                    match property_datum.value {
+                        "Default Building" => default_building = Some(single_value(property_datum)?),
+                        "Mining" => mining = true,
+                        _ => return Err(unknown_property(property_datum)),
+                    }
+                }
+                let default_building = default_building
+                    .ok_or_else(|| InputError::new("Category without a default building").at(category_datum))?;
+                if database.find_category(category_datum.value).is_some() {
+                    return Err(InputError::new(&format!("Duplicate category: {}", category_datum.value)).at(category_datum));
+                }
+                database.categories.push(RecipeCategory {
+                    name: intern(category_datum.value),
+                    default_building: intern(default_building.value),
+                    mining,
+                });
+                default_buildings.push(default_building);
+            }
+        }
+        if let Some(section) = sections.get("Buildings") {
+            for building_datum in section.children.iter() {
+                let proto = parse_building(&database, building_datum)?;
+                if database.find_building(proto.name).is_some() {
+                    return Err(InputError::new(&format!("Duplicate building: {}", building_datum.value)).at(building_datum));
+                }
+                database.buildings.push(proto);
+            }
+        }
+        for building_datum in default_buildings {
+            if database.find_building(building_datum.value).is_none() {
+                return Err(InputError::new(&format!("Unknown building: {}", building_datum.value)).at(building_datum));
+            }
+        }
+        if let Some(section) = sections.get("Recipes") {
+            for recipe_datum in section.children.iter() {
+                let proto = parse_recipe(&database, recipe_datum)?;
+                if database.find_recipe(proto.name).is_some() {
+                    return Err(InputError::new(&format!("Duplicate recipe: {}", recipe_datum.value)).at(recipe_datum));
+                }
+                database.recipes.push(proto);
+            }
+        }
+        if let Some(section) = sections.get("Default Fuel") {
+            let fuel_datum = single_value(section)?;
+            let fuel = parse_resource_name(&database, fuel_datum)?;
+            if fuel.fuel_value.is_none() {
+                return Err(InputError::new(&format!("{} is not a fuel", fuel.name())).at(fuel_datum));
+            }
+            database.default_fuel = Some(fuel);
+        }
+        Ok(database)
+    }
+}
+
+// Writes the database in the format read by `Database::parse`.
+impl fmt::Display for Database {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(fuel) = self.default_fuel {
+            writeln!(f, "Default Fuel: {}", fuel)?;
+            writeln!(f)?;
+        }
+        writeln!(f, "Resources")?;
+        for proto in self.resources.iter() {
+            let resource = proto.resource;
+            writeln!(f, "    {}", resource)?;
+            write_aliases(f, &proto.aliases)?;
+            if resource.mining_hardness != 0.0 {
+                writeln!(f, "        Mining Hardness: {}", resource.mining_hardness)?;
+            }
+            if let Some(fuel_value) = resource.fuel_value {
+                writeln!(f, "        Fuel Value: {}", fuel_value)?;
+            }
+            write_flag(f, "Energy", resource.energy)?;
+            write_flag(f, "Power Grid", resource.power_grid)?;
+            write_flag(f, "Infinite", resource.infinite)?;
+            write_flag(f, "Fluid", resource.fluid)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Categories")?;
+        for category in self.categories.iter() {
+            writeln!(f, "    {}", category.name)?;
+            writeln!(f, "        Default Building: {}", category.default_building)?;
+            write_flag(f, "Mining", category.mining)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Buildings")?;
+        for proto in self.buildings.iter() {
+            writeln!(f, "    {}", proto.name)?;
+            if proto.energy_consumption != 0.0 {
+                writeln!(f, "        Energy Consumption: {}", proto.energy_consumption)?;
+            }
+            if proto.drain != 0.0 {
+                writeln!(f, "        Drain: {}", proto.drain)?;
+            }
+            writeln!(f, "        Crafting Speed: {}", proto.crafting_speed)?;
+            if proto.module_slots != 0 {
+                writeln!(f, "        Module Slots: {}", proto.module_slots)?;
+            }
+            if proto.mining_power != 0.0 {
+                writeln!(f, "        Mining Power: {}", proto.mining_power)?;
+            }
+            if let EnergySource::Burner { efficiency } = proto.energy_source {
+                writeln!(f, "        Burner Efficiency: {}", efficiency)?;
+            }
+            if proto.neighbor_bonus != 0.0 {
+                writeln!(f, "        Neighbor Bonus: {}", proto.neighbor_bonus)?;
+            }
+            if proto.accumulators != 0.0 {
+                writeln!(f, "        Accumulators: {}", proto.accumulators)?;
+            }
+            if proto.pollution != 0.0 {
+                writeln!(f, "        Pollution: {}", proto.pollution)?;
+            }
+            writeln!(f, "        Categories")?;
+            for category in proto.categories.iter() {
+                writeln!(f, "            {}", category.name)?;
+            }
+        }
+        writeln!(f)?;
+        writeln!(f, "Recipes")?;
+        for proto in self.recipes.iter() {
+            writeln!(f, "    {}", proto.name)?;
+            write_aliases(f, &proto.aliases)?;
+            writeln!(f, "        Category: {}", proto.category.name)?;
+            writeln!(f, "        Time: {}", proto.time)?;
+            write_flag(f, "Productivity", proto.productivity)?;
+            write_amounts(f, "        ", &proto.inputs, &proto.outputs)?;
+            // Only the expensive costs that differ are written, so recipes
+            // that cost the same in both modes get a bare Expensive.
+            if let Some(ref cost) = proto.expensive {
+                writeln!(f, "        Expensive")?;
+                if cost.time != proto.time {
+                    writeln!(f, "            Time: {}", cost.time)?;
+                }
+                let inputs : &[(Resource, f32)] = if cost.inputs != proto.inputs { &cost.inputs } else { &[] };
+                let outputs : &[(Resource, f32)] = if cost.outputs != proto.outputs { &cost.outputs } else { &[] };
+                write_amounts(f, "            ", inputs, outputs)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_amounts(f: &mut fmt::Formatter, indent: &str, inputs: &[(Resource, f32)], outputs: &[(Resource, f32)]) -> fmt::Result {
+    for &(label, amounts) in [("Inputs", inputs), ("Outputs", outputs)].iter() {
+        if !amounts.is_empty() {
+            writeln!(f, "{}{}", indent, label)?;
+            for &(resource, amount) in amounts.iter() {
+                writeln!(f, "{}    {}: {}", indent, resource, amount)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_aliases(f: &mut fmt::Formatter, aliases: &[&str]) -> fmt::Result {
+    if !aliases.is_empty() {
+        writeln!(f, "        Aliases")?;
+        for alias in aliases.iter() {
+            writeln!(f, "            {}", alias)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_flag(f: &mut fmt::Formatter, flag: &str, set: bool) -> fmt::Result {
+    if set {
+        writeln!(f, "        {}", flag)?;
+    }
+    Ok(())
+}
+
+fn parse_resource(datum: &Data) -> Result<ProtoResource, InputError> {
+    let mut resource = Resource::intern(datum.value);
+    let mut aliases = Vec::new();
+    for property_datum in datum.children.iter() {
+        match property_datum.value {
+            "Aliases" => aliases.extend(property_datum.children.iter().map(|alias| intern(alias.value))),
+            "Mining Hardness" => resource.mining_hardness = parse_number(property_datum)?,
+            "Fuel Value" => resource.fuel_value = Some(parse_number(property_datum)?),
+            "Energy" => resource.energy = true,
+            "Power Grid" => resource.power_grid = true,
+            "Infinite" => resource.infinite = true,
+            "Fluid" => resource.fluid = true,
+            _ => return Err(unknown_property(property_datum)),
+        }
+    }
+    Ok(ProtoResource {
+        resource,
+        aliases,
+    })
+}
+
+fn parse_building(database: &Database, datum: &Data) -> Result<ProtoBuilding, InputError> {
+    let mut proto = ProtoBuilding {
+        name: intern(datum.value),
+        energy_consumption: 0.0,
+        drain: 0.0,
+        crafting_speed: 1.0,
+        module_slots: 0,
+        mining_power: 0.0,
+        energy_source: EnergySource::Electric,
+        categories: Vec::new(),
+        neighbor_bonus: 0.0,
+        accumulators: 0.0,
+        pollution: 0.0,
+    };
+    for property_datum in datum.children.iter() {
+        match property_datum.value {
+            "Energy Consumption" => proto.energy_consumption = parse_number(property_datum)?,
+            "Drain" => proto.drain = parse_number(property_datum)?,
+            "Crafting Speed" => proto.crafting_speed = parse_number(property_datum)?,
+            "Module Slots" => {
+                let value_datum = single_value(property_datum)?;
+                proto.module_slots = value_datum.value.parse()
+                    .map_err(|_| InputError::new("Invalid module slot count").at(value_datum))?;
+            },
+            "Mining Power" => proto.mining_power = parse_number(property_datum)?,
+            "Burner Efficiency" => proto.energy_source = EnergySource::Burner { efficiency: parse_number(property_datum)? },
+            "Neighbor Bonus" => proto.neighbor_bonus = parse_number(property_datum)?,
+            "Accumulators" => proto.accumulators = parse_number(property_datum)?,
+            "Pollution" => proto.pollution = parse_number(property_datum)?,
+            "Categories" => {
+                for category_datum in property_datum.children.iter() {
+                    let category = database.find_category(category_datum.value)
+                        .ok_or_else(|| InputError::new(&format!("Unknown category: {}", category_datum.value)).at(category_datum))?;
+                    proto.categories.push(category);
+                }
+            },
+            _ => return Err(unknown_property(property_datum)),
+        }
+    }
+    Ok(proto)
+}
+
+fn parse_recipe(database: &Database, datum: &Data) -> Result<ProtoRecipe, InputError> {
+    let mut aliases = Vec::new();
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+    let mut time = None;
+    let mut category = None;
+    let mut productivity = false;
+    let mut expensive_datum = None;
+    for property_datum in datum.children.iter() {
+        match property_datum.value {
+            "Aliases" => aliases.extend(property_datum.children.iter().map(|alias| intern(alias.value))),
+            "Category" => {
+                let category_datum = single_value(property_datum)?;
+                category = Some(database.find_category(category_datum.value)
+                    .ok_or_else(|| InputError::new(&format!("Unknown category: {}", category_datum.value)).at(category_datum))?);
+            },
+            "Time" => time = Some(parse_number(property_datum)?),
+            "Productivity" => productivity = true,
+            "Inputs" => inputs = parse_amounts(database, property_datum)?,
+            "Outputs" => outputs = parse_amounts(database, property_datum)?,
+            "Expensive" => expensive_datum = Some(property_datum),
+            _ => return Err(unknown_property(property_datum)),
+        }
+    }
+    let time = time.ok_or_else(|| InputError::new("Recipe without a time").at(datum))?;
+    // Expensive costs default to the normal ones, so only the parts that
+    // differ need to be given.
+    let expensive = match expensive_datum {
+        Some(expensive_datum) => {
+            let mut cost = RecipeCost {
+                inputs: inputs.clone(),
+                outputs: outputs.clone(),
+                time,
+            };
+            for property_datum in expensive_datum.children.iter() {
+                match property_datum.value {
+                    "Time" => cost.time = parse_number(property_datum)?,
+                    "Inputs" => cost.inputs = parse_amounts(database, property_datum)?,
+                    "Outputs" => cost.outputs = parse_amounts(database, property_datum)?,
+                    _ => return Err(unknown_property(property_datum)),
+                }
+            }
+            Some(cost)
+        },
+        None => None,
+    };
+    Ok(ProtoRecipe {
+        name: intern(datum.value),
+        aliases,
+        inputs,
+        outputs,
+        time,
+        category: category.ok_or_else(|| InputError::new("Recipe without a category").at(datum))?,
+        productivity,
+        expensive,
+    })
+}
+
+fn parse_amounts(database: &Database, datum: &Data) -> Result<Vec<(Resource, f32)>, InputError> {
+    datum.children.iter()
+        .map(|amount_datum| Ok((parse_resource_name(database, amount_datum)?, parse_number(amount_datum)?)))
+        .collect()
+}
+
+fn parse_resource_name(database: &Database, datum: &Data) -> Result<Resource, InputError> {
+    database.find_resource(datum.value)
+        .ok_or_else(|| InputError::new(&format!("Unknown resource: {}", datum.value)).at(datum))
+}
+
+fn single_value<'a, 'b>(datum: &'a Data<'b>) -> Result<&'a Data<'b>, InputError> {
+    if datum.children.len() != 1 {
+        return Err(InputError::new(&format!("Expected a single value for {}", datum.value)).at(datum));
+    }
+    Ok(&datum.children[0])
+}
+
+fn parse_number(datum: &Data) -> Result<f32, InputError> {
+    let value_datum = single_value(datum)?;
+    value_datum.value.parse()
+        .map_err(|_| InputError::new(&format!("Invalid number for {}", datum.value)).at(value_datum))
+}
+
+fn unknown_property(datum: &Data) -> InputError {
+    InputError::new(&format!("Unknown property: {}", datum.value)).at(datum)
+}
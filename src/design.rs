@@ -0,0 +1,1613 @@
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use analysis::Analysis;
+use database::Database;
+use building::{Beacons, Building, EnergySource, ProtoBuilding, BEACON_MODULE_SLOTS};
+use error::{AnalyzeError, InputError};
+use logistics::{pipe_throughput, BELTS, DEFAULT_PIPE_LENGTH, INSERTERS, INSERTERS_PER_SIDE, PUMP_SPACING, PUMP_THROUGHPUT};
+use modules::Module;
+use optimize::Objective;
+use parse::{clean, Data};
+use recipe::{ProtoRecipe, Recipe, RecipeMode};
+use research::{Research, MAX_INSERTER_CAPACITY};
+use resource::Resource;
+use serde_json::{self, Map, Number, Value};
+use simplex::{LinearProgram, Relation};
+use system::{LinearSystem, SystemError};
+
+// Relative slack allowed when checking building counts against their bounds,
+// so that rounding in the solution does not trip them.
+const COUNT_TOLERANCE : f32 = 1e-4;
+
+// How far, relative to the largest value in the solution, a count or rate may
+// fall on the wrong side of zero before it is reported.
+const SIGN_TOLERANCE : f64 = 1e-6;
+// Overflow surpluses smaller than this are rounding in the linear program.
+const MIN_SURPLUS : f64 = 1e-9;
+// The largest multiplier tried when looking for whole-number building counts.
+const MAX_WHOLE_MULTIPLIER : u32 = 100;
+// Recipes that have been replaced by one that takes its fuel from a Fuel
+// section, along with the fuel the old recipe burned.
+const FUELED_RECIPES : [(&str, &str, &str); 2] = [
+    ("Boiling (Coal)", "Boiling", "Coal"),
+    ("Boiling (Solid Fuel)", "Boiling", "Solid fuel"),
+];
+
+#[derive(Debug, Clone)]
+pub struct ResourceLine {
+    pub name: String,
+    pub resource_type: Resource,
+    pub(crate) index: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Design {
+    pub resource_lines: HashMap<String, ResourceLine>,
+    pub buildings: Vec<Building>,
+    pub input_lines: HashSet<String>,
+    pub output_lines: HashSet<String>,
+    // Lines that may end up with a surplus, such as byproducts nothing else
+    // in the design uses. The surplus is kept as small as the design allows.
+    pub overflow_lines: HashSet<String>,
+    pub targets: Vec<(String, f32)>,
+    pub research: Research,
+    pub recipe_mode: RecipeMode,
+    // The number of pipe segments fluid lines run through, for planning
+    // pipes and pumps.
+    pub pipe_lengths: HashMap<String, u32>,
+    // What to minimize when the design is optimized rather than analyzed.
+    pub objective: Option<Objective>,
+    // Things worth pointing out about the design that do not stop it from
+    // being analyzed.
+    pub warnings: Vec<String>,
+    // The game data the design was read with. Everything the design needs
+    // from it is copied in while the design is read.
+    database: Arc<Database>,
+    pub(crate) next_index: usize,
+}
+
+impl Design {
+    fn new() -> Design {
+        Design {
+            resource_lines: HashMap::new(),
+            buildings: Vec::new(),
+            input_lines: HashSet::new(),
+            output_lines: HashSet::new(),
+            overflow_lines: HashSet::new(),
+            targets: Vec::new(),
+            research: Research::default(),
+            recipe_mode: RecipeMode::default(),
+            pipe_lengths: HashMap::new(),
+            objective: None,
+            warnings: Vec::new(),
+            database: Database::current(),
+            next_index: 0,
+        }
+    }
+
+    // Parses a design file. Comments and blank lines are allowed anywhere.
+    pub fn parse(contents: &[u8]) -> Result<Design, InputError> {
+        Design::parse_with(&Database::current(), contents)
+    }
+
+    // Parses a design file against the given game data.
+    pub fn parse_with(database: &Arc<Database>, contents: &[u8]) -> Result<Design, InputError> {
+        let _active = Database::activate(database);
+        Design::parse_in(contents, Path::new(""), &[])
+    }
+
+    // Included designs are found relative to `dir`. `including` holds the
+    // files currently being read, so that an include cycle can be caught.
+    fn parse_in(contents: &[u8], dir: &Path, including: &[PathBuf]) -> Result<Design, InputError> {
+        let cleaned = clean(contents);
+        Data::from_cleaned(&cleaned)
+            .and_then(|data| Design::from_data(data, dir, including))
+            .map_err(|e| e.in_source(contents))
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Design, InputError> {
+        Design::from_file_with(&Database::current(), path)
+    }
+
+    pub fn from_file_with<P: AsRef<Path>>(database: &Arc<Database>, path: P) -> Result<Design, InputError> {
+        let _active = Database::activate(database);
+        Design::read_file(path.as_ref(), &[])
+    }
+
+    // The game data the design was read with.
+    pub fn database(&self) -> &Arc<Database> {
+        &self.database
+    }
+
+    fn read_file(path: &Path, including: &[PathBuf]) -> Result<Design, InputError> {
+        let mut contents = Vec::new();
+        File::open(path)
+            .and_then(|mut input| input.read_to_end(&mut contents))
+            .map_err(|e| InputError::new(&format!("Unable to read {}: {}", path.display(), e)))?;
+        let mut including = including.to_vec();
+        including.push(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        Design::parse_in(&contents, dir, &including).map_err(|e| e.in_file(&path.display().to_string()))
+    }
+
+    fn get_line(&mut self, resource_type: Resource, name: &str) -> Result<ResourceLine, InputError> {
+        if resource_type.is_power_grid() && !self.resource_lines.contains_key(name) {
+            if let Some(grid) = self.power_grid() {
+                return Err(InputError::new(&format!("Only one power grid is supported, found Electricity lines {} and {}", grid.name, name)));
+            }
+        }
+        match self.resource_lines.entry(name.to_string()) {
+            Entry::Occupied(ent) => {
+                let existing_line = ent.get();
+                if existing_line.resource_type == resource_type {
+                    Ok(existing_line.clone())
+                } else {
+                    Err(InputError::new(&format!("Resource type mismatch: {}", name)))
+                }
+            },
+            Entry::Vacant(ent) => {
+                let new_line = ResourceLine {
+                    name: name.to_string(),
+                    resource_type,
+                    index: self.next_index,
+                };
+                self.next_index += 1;
+                ent.insert(new_line.clone());
+                Ok(new_line)
+            },
+        }
+    }
+
+    // The Electricity line that all electric buildings draw from, if any.
+    pub fn power_grid(&self) -> Option<&ResourceLine> {
+        self.resource_lines.values().find(|line| line.resource_type.is_power_grid())
+    }
+
+    fn from_data(data: Vec<Data>, dir: &Path, including: &[PathBuf]) -> Result<Design, InputError> {
+        let mut design = Design::new();
+        let mut recipe_choices = HashMap::new();
+        // Where each line is first consumed and produced by a building, so
+        // that lines with a misspelled name can be pointed out.
+        let mut consumed_at = HashMap::new();
+        let mut produced_at = HashMap::new();
+        let mut pipe_length_at = Vec::new();
+        let mut overflow_at = HashMap::new();
+        let mut allowed_modules_at = None;
+        let mut recipes_at = None;
+        // The recipe mode decides the costs of every recipe in the design, so
+        // it is read before anything else.
+        for datum in data.iter().filter(|datum| datum.value == "Recipe Mode") {
+            if datum.children.len() != 1 {
+                return Err(InputError::new("Malformed recipe mode").at(datum));
+            }
+            design.recipe_mode = RecipeMode::from_str(datum.children[0].value).map_err(|e| e.at(&datum.children[0]))?;
+        }
+        for datum in data {
+            if datum.value == "Recipe Mode" {
+                continue;
+            } else if datum.value == "Inputs" {
+                // Read input lines
+                for input_datum in datum.children {
+                    let resource_type = Resource::from_str(input_datum.value).map_err(|e| e.at(&input_datum))?;
+                    for input_line in input_datum.children {
+                        if !input_line.children.is_empty() {
+                            return Err(InputError::new("Unexpected child of input line name").at(&input_line.children[0]));
+                        }
+                        let resource_line = design.get_line(resource_type, input_line.value).map_err(|e| e.at(&input_line))?;
+                        design.input_lines.insert(resource_line.name);
+                    }
+                }
+            } else if datum.value == "Outputs" {
+                // Read output lines
+                for output_datum in datum.children {
+                    let resource_type = Resource::from_str(output_datum.value).map_err(|e| e.at(&output_datum))?;
+                    for output_line in output_datum.children {
+                        if !output_line.children.is_empty() {
+                            return Err(InputError::new("Unexpected child of output line name").at(&output_line.children[0]));
+                        }
+                        let resource_line = design.get_line(resource_type, output_line.value).map_err(|e| e.at(&output_line))?;
+                        design.output_lines.insert(resource_line.name);
+                    }
+                }
+            } else if datum.value == "Overflow" || datum.value == "Sink" {
+                // Read overflow lines
+                for overflow_datum in datum.children {
+                    let resource_type = Resource::from_str(overflow_datum.value).map_err(|e| e.at(&overflow_datum))?;
+                    for overflow_line in overflow_datum.children {
+                        if !overflow_line.children.is_empty() {
+                            return Err(InputError::new("Unexpected child of overflow line name").at(&overflow_line.children[0]));
+                        }
+                        let resource_line = design.get_line(resource_type, overflow_line.value).map_err(|e| e.at(&overflow_line))?;
+                        overflow_at.entry(resource_line.name.clone())
+                            .or_insert((overflow_line.line, overflow_line.column));
+                        design.overflow_lines.insert(resource_line.name);
+                    }
+                }
+            } else if datum.value == "Targets" {
+                // Read target values
+                for target_datum in datum.children {
+                    let target_line_name = target_datum.value;
+                    if target_datum.children.len() == 1 {
+                        // Rates may be written with an explicit "/s" unit.
+                        let target_str = target_datum.children[0].value.trim_end_matches("/s");
+                        let target_val = match target_str.parse() {
+                            Ok(v) => v,
+                            Err(_) => return Err(InputError::new("Invalid target value").at(&target_datum.children[0])),
+                        };
+                        design.targets.push((target_line_name.to_string(), target_val));
+                    } else {
+                        return Err(InputError::new("Malformed target value").at(&target_datum));
+                    }
+                }
+            } else if datum.value == "Research" {
+                for research_datum in datum.children.iter() {
+                    if research_datum.children.len() != 1 {
+                        return Err(InputError::new("Malformed research level").at(research_datum));
+                    }
+                    let level_datum = &research_datum.children[0];
+                    let level = level_datum.value.parse()
+                        .map_err(|_| InputError::new("Malformed research level").at(level_datum))?;
+                    if research_datum.value.to_lowercase() == "mining productivity" {
+                        design.research.mining_productivity = level;
+                    } else if research_datum.value.to_lowercase() == "inserter capacity" {
+                        if level > MAX_INSERTER_CAPACITY {
+                            return Err(InputError::new(&format!("Inserter capacity research only goes up to level {}", MAX_INSERTER_CAPACITY)).at(level_datum));
+                        }
+                        design.research.inserter_capacity = level;
+                    } else {
+                        return Err(InputError::new(&format!("Unknown research: {}", research_datum.value)).at(research_datum));
+                    }
+                }
+            } else if datum.value == "Optimize" {
+                if datum.children.len() != 1 {
+                    return Err(InputError::new("Malformed objective").at(&datum));
+                }
+                design.objective = Some(Objective::from_str(datum.children[0].value).map_err(|e| e.at(&datum.children[0]))?);
+            } else if datum.value == "Pipe Lengths" {
+                for length_datum in datum.children.iter() {
+                    if length_datum.children.len() != 1 {
+                        return Err(InputError::new("Malformed pipe length").at(length_datum));
+                    }
+                    let length = match length_datum.children[0].value.parse::<u32>() {
+                        Ok(length) if length > 0 => length,
+                        _ => return Err(InputError::new("Invalid pipe length").at(&length_datum.children[0])),
+                    };
+                    pipe_length_at.push((length_datum.value.to_string(), length_datum.line, length_datum.column));
+                    design.pipe_lengths.insert(length_datum.value.to_string(), length);
+                }
+            } else if datum.value == "Include" {
+                design.include(&datum, dir, including, &mut consumed_at, &mut produced_at)?;
+                if allowed_modules_at.is_none() && design.buildings.iter().any(|building| !building.allowed_modules.is_empty()) {
+                    allowed_modules_at = Some((datum.line, datum.column, datum.value));
+                }
+            } else if datum.value == "Recipes" {
+                // Read recipe choices for planned buildings
+                recipes_at.get_or_insert((datum.line, datum.column, datum.value));
+                for recipe_datum in datum.children {
+                    let resource_type = Resource::from_str(recipe_datum.value).map_err(|e| e.at(&recipe_datum))?;
+                    if recipe_datum.children.len() != 1 {
+                        return Err(InputError::new("Malformed recipe choice").at(&recipe_datum));
+                    }
+                    let recipe_name = recipe_datum.children[0].value;
+                    let proto_recipe = ProtoRecipe::from_name(recipe_name, design.recipe_mode)
+                        .ok_or_else(|| unknown_recipe(recipe_name).at(&recipe_datum.children[0]))?;
+                    if proto_recipe.outputs.iter().all(|&(output, _)| output != resource_type) {
+                        return Err(InputError::new(&format!("Recipe {} does not produce {}", recipe_name, recipe_datum.value)).at(&recipe_datum.children[0]));
+                    }
+                    recipe_choices.insert(resource_type, proto_recipe);
+                }
+            } else {
+                // Read a building description
+                let proto_building = ProtoBuilding::from_name(datum.value)
+                    .ok_or_else(|| InputError::new(&format!("Unknown building: {}", datum.value)).at(&datum))?;
+                if datum.children.is_empty() {
+                    return Err(InputError::new("Found building with no recipe").at(&datum));
+                }
+                let recipe_name = datum.children[0].value;
+                let proto_recipe = ProtoRecipe::from_name(recipe_name, design.recipe_mode)
+                    .ok_or_else(|| unknown_recipe(recipe_name).at(&datum.children[0]))?;
+                if !proto_building.accepts(proto_recipe.category) {
+                    return Err(InputError::new(&format!("{} cannot run {}; it needs a building such as {}",
+                                                        proto_building.name, proto_recipe.name, proto_recipe.category.default_building))
+                        .at(&datum.children[0]));
+                }
+
+                let mut required_inputs : HashMap<Resource, f32> = proto_recipe.inputs.iter().cloned().collect();
+                let mut required_outputs : HashMap<Resource, f32> = proto_recipe.outputs.iter().cloned().collect();
+                let mut line_inputs = Vec::new();
+                let mut line_outputs = Vec::new();
+                let mut modules = Vec::new();
+                let mut allowed_modules = Vec::new();
+                let mut beacons = None;
+                let mut fuel = None;
+                let mut neighbor_bonus = 0.0;
+                let mut count = None;
+                let mut minimum_count = None;
+                let mut maximum_count = None;
+                let mut crafting_speed = proto_building.crafting_speed_for(&proto_recipe);
+                for property_datum in datum.children[1..].iter() {
+                    if property_datum.value == "Inputs" {
+                        for input_datum in property_datum.children.iter() {
+                            let resource_type = Resource::from_str(input_datum.value).map_err(|e| e.at(input_datum))?;
+
+                            if input_datum.children.len() != 1 {
+                                return Err(InputError::new("Only single line per input is supported.").at(input_datum));
+                            }
+                            let line_name = input_datum.children[0].value;
+
+                            let resource_line = design.get_line(resource_type, line_name).map_err(|e| e.at(&input_datum.children[0]))?;
+                            consumed_at.entry(resource_line.name.clone()).or_insert_with(|| {
+                                let line_datum = &input_datum.children[0];
+                                (line_datum.line, line_datum.column)
+                            });
+
+                            match required_inputs.remove(&resource_type) {
+                                Some(qty) => {
+                                    line_inputs.push((resource_line, qty));
+                                },
+                                None => {
+                                    return Err(InputError::new(&format!("Invalid recipe input for {}", proto_recipe.name)).at(input_datum));
+                                },
+                            }
+                        }
+                    } else if property_datum.value == "Outputs" {
+                        for output_datum in property_datum.children.iter() {
+                            let resource_type = Resource::from_str(output_datum.value).map_err(|e| e.at(output_datum))?;
+
+                            if output_datum.children.len() != 1 {
+                                return Err(InputError::new("Only single line per output is supported.").at(output_datum));
+                            }
+                            let line_name = output_datum.children[0].value;
+
+                            let resource_line = design.get_line(resource_type, line_name).map_err(|e| e.at(&output_datum.children[0]))?;
+                            produced_at.entry(resource_line.name.clone()).or_insert_with(|| {
+                                let line_datum = &output_datum.children[0];
+                                (line_datum.line, line_datum.column)
+                            });
+
+                            match required_outputs.remove(&resource_type) {
+                                Some(qty) => {
+                                    line_outputs.push((resource_line, qty));
+                                },
+                                None => {
+                                    return Err(InputError::new(&format!("Invalid recipe output for {}", proto_recipe.name)).at(output_datum));
+                                },
+                            }
+                        }
+                    } else if property_datum.value == "Modules" {
+                        let building_modules = parse_modules(property_datum).map_err(|e| e.at(property_datum))?;
+                        check_productivity(property_datum, &building_modules, &proto_recipe)?;
+                        modules.extend(building_modules);
+                        let module_count : i16 = modules.iter().map(|&(_, count)| count).sum();
+                        if module_count > proto_building.module_slots {
+                            return Err(InputError::new(&format!("{} has {} module slots, but {} modules were given",
+                                                                proto_building.name, proto_building.module_slots, module_count))
+                                .at(property_datum));
+                        }
+                    } else if property_datum.value == "Allowed Modules" {
+                        if proto_building.module_slots == 0 {
+                            return Err(InputError::new(&format!("{} has no module slots", proto_building.name)).at(property_datum));
+                        }
+                        let allowed = parse_modules(property_datum).map_err(|e| e.at(property_datum))?;
+                        allowed_modules_at.get_or_insert((property_datum.line, property_datum.column, property_datum.value));
+                        check_productivity(property_datum, &allowed, &proto_recipe)?;
+                        allowed_modules.extend(allowed.into_iter().map(|(module, _)| module));
+                    } else if property_datum.value == "Fuel" {
+                        if proto_building.energy_source == EnergySource::Electric {
+                            return Err(InputError::new(&format!("{} does not burn fuel", proto_building.name)).at(property_datum));
+                        }
+                        if property_datum.children.len() != 1 {
+                            return Err(InputError::new("Only a single fuel is supported").at(property_datum));
+                        }
+                        let fuel_datum = &property_datum.children[0];
+                        let resource_type = Resource::from_str(fuel_datum.value).map_err(|e| e.at(fuel_datum))?;
+                        if resource_type.fuel_value().is_none() {
+                            return Err(InputError::new(&format!("{} is not a fuel", resource_type.name())).at(fuel_datum));
+                        }
+                        if fuel_datum.children.len() != 1 {
+                            return Err(InputError::new("Only single line per fuel is supported.").at(fuel_datum));
+                        }
+                        let line_datum = &fuel_datum.children[0];
+                        let resource_line = design.get_line(resource_type, line_datum.value).map_err(|e| e.at(line_datum))?;
+                        consumed_at.entry(resource_line.name.clone())
+                            .or_insert((line_datum.line, line_datum.column));
+                        fuel = Some(resource_line);
+                    } else if property_datum.value == "Layout" {
+                        if proto_building.neighbor_bonus == 0.0 {
+                            return Err(InputError::new("Only nuclear reactors have a layout").at(property_datum));
+                        }
+                        if property_datum.children.len() != 1 {
+                            return Err(InputError::new("Invalid reactor layout").at(property_datum));
+                        }
+                        // Layouts are given as rows by columns of reactors, such as 2x4.
+                        let layout_datum = &property_datum.children[0];
+                        let dimensions : Vec<Result<u32, _>> = layout_datum.value.split('x').map(|d| d.trim().parse()).collect();
+                        match dimensions.as_slice() {
+                            &[Ok(rows), Ok(columns)] if rows > 0 && columns > 0 => {
+                                neighbor_bonus = proto_building.layout_neighbor_bonus(rows, columns);
+                            },
+                            _ => return Err(InputError::new("Invalid reactor layout").at(layout_datum)),
+                        }
+                    } else if property_datum.value == "Yield" {
+                        if !proto_recipe.outputs.iter().any(|&(output, _)| output.is_infinite()) {
+                            return Err(InputError::new("Only recipes mining infinite resources have a yield").at(property_datum));
+                        }
+                        if property_datum.children.len() != 1 {
+                            return Err(InputError::new("Invalid yield").at(property_datum));
+                        }
+                        // Yields are given as a percentage, as shown in game.
+                        let yield_datum = &property_datum.children[0];
+                        let yield_percent : f32 = match yield_datum.value.trim_end_matches('%').parse() {
+                            Ok(y) if y > 0.0 => y,
+                            Ok(_) => return Err(InputError::new("Yields are more than 0%").at(yield_datum)),
+                            Err(_) => return Err(InputError::new("Invalid yield").at(yield_datum)),
+                        };
+                        crafting_speed *= yield_percent / 100.0;
+                    } else if property_datum.value == "Count" || property_datum.value == "Minimum Count" || property_datum.value == "Maximum Count" {
+                        if property_datum.children.len() != 1 {
+                            return Err(InputError::new("Invalid building count").at(property_datum));
+                        }
+                        let count_datum = &property_datum.children[0];
+                        let value = match count_datum.value.parse::<f32>() {
+                            Ok(n) if n >= 0.0 => n,
+                            _ => return Err(InputError::new("Invalid building count").at(count_datum)),
+                        };
+                        match property_datum.value {
+                            "Count" => count = Some(value),
+                            "Minimum Count" => minimum_count = Some(value),
+                            _ => maximum_count = Some(value),
+                        }
+                    } else if property_datum.value == "Beacons" {
+                        let mut beacon_count = None;
+                        let mut beacon_modules = Vec::new();
+                        let mut shared_by = 1.0;
+                        for beacon_datum in property_datum.children.iter() {
+                            if beacon_datum.value == "Count" {
+                                if beacon_datum.children.len() != 1 {
+                                    return Err(InputError::new("Invalid beacon count").at(beacon_datum));
+                                }
+                                beacon_count = match beacon_datum.children[0].value.parse() {
+                                    Ok(n) if n > 0 => Some(n),
+                                    _ => return Err(InputError::new("Invalid beacon count").at(&beacon_datum.children[0])),
+                                };
+                            } else if beacon_datum.value == "Shared By" {
+                                if beacon_datum.children.len() != 1 {
+                                    return Err(InputError::new("Invalid beacon sharing").at(beacon_datum));
+                                }
+                                // A beacon reaches at least the building it is listed on.
+                                shared_by = match beacon_datum.children[0].value.parse() {
+                                    Ok(n) if n >= 1.0 => n,
+                                    _ => return Err(InputError::new("Beacons are shared by at least 1 building").at(&beacon_datum.children[0])),
+                                };
+                            } else if beacon_datum.value == "Modules" {
+                                let modules_in_beacon = parse_modules(beacon_datum).map_err(|e| e.at(beacon_datum))?;
+                                for (module_datum, &(module_type, _)) in beacon_datum.children.iter().zip(modules_in_beacon.iter()) {
+                                    if module_type.is_productivity() {
+                                        return Err(InputError::new("Beacons cannot hold productivity modules").at(module_datum));
+                                    }
+                                }
+                                beacon_modules.extend(modules_in_beacon);
+                                let module_count : i16 = beacon_modules.iter().map(|&(_, count)| count).sum();
+                                if module_count > BEACON_MODULE_SLOTS {
+                                    return Err(InputError::new(&format!("Beacons have {} module slots, but {} modules were given",
+                                                                        BEACON_MODULE_SLOTS, module_count))
+                                        .at(beacon_datum));
+                                }
+                            } else {
+                                return Err(InputError::new(&format!("Unknown beacon property: {}", beacon_datum.value)).at(beacon_datum));
+                            }
+                        }
+                        let beacon_count = beacon_count.ok_or_else(|| InputError::new("Beacons with no count").at(property_datum))?;
+                        beacons = Some(Beacons {
+                            count: beacon_count,
+                            modules: beacon_modules,
+                            shared_by,
+                        });
+                    } else {
+                        return Err(InputError::new(&format!("Unknown building property: {}", property_datum.value)).at(property_datum));
+                    }
+                }
+
+                if !required_inputs.is_empty() {
+                    let missing : Vec<&str> = required_inputs.keys().map(|resource| resource.name()).collect();
+                    return Err(InputError::new(&format!("Not all inputs are filled, missing {}", missing.join(", "))).at(&datum.children[0]));
+                }
+                if !required_outputs.is_empty() {
+                    let missing : Vec<&str> = required_outputs.keys().map(|resource| resource.name()).collect();
+                    return Err(InputError::new(&format!("Not all outputs are filled, missing {}", missing.join(", "))).at(&datum.children[0]));
+                }
+                if proto_building.energy_source != EnergySource::Electric && fuel.is_none() {
+                    return Err(InputError::new(&format!("{} needs a Fuel", proto_building.name)).at(&datum));
+                }
+                if !modules.is_empty() && !allowed_modules.is_empty() {
+                    return Err(InputError::new("Give either Modules or Allowed Modules, not both").at(&datum));
+                }
+                if count.is_some() && (minimum_count.is_some() || maximum_count.is_some()) {
+                    return Err(InputError::new("A building with a fixed Count cannot also have a minimum or maximum").at(&datum));
+                }
+                if let (Some(minimum), Some(maximum)) = (minimum_count, maximum_count) {
+                    if minimum > maximum {
+                        return Err(InputError::new("Minimum Count is more than Maximum Count").at(&datum));
+                    }
+                }
+
+                let recipe = Recipe {
+                    name: proto_recipe.name,
+                    inputs: line_inputs,
+                    outputs: line_outputs,
+                    time: proto_recipe.time,
+                    category: proto_recipe.category,
+                };
+
+                let building = design.add_building(&proto_building, recipe, crafting_speed, modules, beacons, fuel);
+                building.neighbor_bonus = neighbor_bonus;
+                building.count = count;
+                building.minimum_count = minimum_count;
+                building.maximum_count = maximum_count;
+                building.allowed_modules = allowed_modules;
+            }
+        }
+        for (line_name, &(line, column)) in consumed_at.iter() {
+            if !produced_at.contains_key(line_name) && !design.input_lines.contains(line_name) {
+                return Err(InputError::new(&format!("Line {} is consumed but never produced or declared as an input", line_name))
+                    .at_position(line, column, line_name));
+            }
+        }
+        for (line_name, &(line, column)) in produced_at.iter() {
+            // Power grids are consumed by every electric building.
+            let is_grid = design.resource_lines[line_name].resource_type.is_power_grid();
+            let is_overflow = design.overflow_lines.contains(line_name);
+            if !consumed_at.contains_key(line_name) && !design.output_lines.contains(line_name) && !is_overflow && !is_grid {
+                return Err(InputError::new(&format!("Line {} is produced but never consumed or declared as an output", line_name))
+                    .at_position(line, column, line_name));
+            }
+        }
+        for (line_name, &(line, column)) in overflow_at.iter() {
+            if design.input_lines.contains(line_name) || design.output_lines.contains(line_name) {
+                return Err(InputError::new(&format!("Line {} cannot be both an overflow and an input or output", line_name))
+                    .at_position(line, column, line_name));
+            }
+        }
+
+        if design.objective.is_none() {
+            if let Some((line, column, text)) = allowed_modules_at {
+                return Err(InputError::new("Allowed Modules are only used when the design has an Optimize objective")
+                    .at_position(line, column, text));
+            }
+        }
+        for (line_name, line, column) in pipe_length_at {
+            match design.resource_lines.get(&line_name) {
+                Some(resource_line) if resource_line.resource_type.is_fluid() => {},
+                Some(_) => return Err(InputError::new(&format!("Line {} does not carry a fluid", line_name)).at_position(line, column, &line_name)),
+                None => return Err(InputError::new(&format!("No such line: {}", line_name)).at_position(line, column, &line_name)),
+            }
+        }
+
+        if design.buildings.is_empty() {
+            design.plan_buildings(&recipe_choices)?;
+        } else if let Some((line, column, text)) = recipes_at {
+            return Err(InputError::new("Recipes can only be chosen for designs without buildings").at_position(line, column, text));
+        }
+        for building in design.buildings.iter_mut() {
+            building.base_productivity = design.research.base_productivity(building.recipe.category);
+        }
+        if design.recipe_mode == RecipeMode::Expensive {
+            // Recipes the data gives no expensive costs for fall back to
+            // their normal costs, which may well be wrong.
+            let database = Database::current();
+            let mut fallbacks : Vec<&str> = design.buildings.iter()
+                .filter(|building| database.find_recipe(building.recipe.name).is_some_and(|proto| proto.expensive.is_none()))
+                .map(|building| building.recipe.name)
+                .collect();
+            fallbacks.sort();
+            fallbacks.dedup();
+            for recipe_name in fallbacks {
+                let warning = format!("{} has no expensive costs in the game data, so its normal costs are used", recipe_name);
+                if !design.warnings.contains(&warning) {
+                    design.warnings.push(warning);
+                }
+            }
+        }
+        Ok(design)
+    }
+
+    // Adds the buildings of another design file. Each entry under `Lines`
+    // connects an input or output line of the included design to a line of
+    // this one. Every other line of the included design is renamed to
+    // "name/line", where the name defaults to the file name, and its inputs
+    // and outputs that are not connected stay inputs and outputs, as do its
+    // overflow lines. Targets and research of the included design are not
+    // used.
+    fn include(&mut self, datum: &Data, dir: &Path, including: &[PathBuf],
+               consumed_at: &mut HashMap<String, (usize, usize)>,
+               produced_at: &mut HashMap<String, (usize, usize)>) -> Result<(), InputError> {
+        let mut file_datum = None;
+        let mut name_datum = None;
+        let mut connections = Vec::new();
+        for property_datum in datum.children.iter() {
+            if property_datum.value == "File" || property_datum.value == "Name" {
+                if property_datum.children.len() != 1 {
+                    return Err(InputError::new(&format!("Malformed include {}", property_datum.value.to_lowercase())).at(property_datum));
+                }
+                if property_datum.value == "File" {
+                    file_datum = Some(&property_datum.children[0]);
+                } else {
+                    name_datum = Some(&property_datum.children[0]);
+                }
+            } else if property_datum.value == "Lines" {
+                for line_datum in property_datum.children.iter() {
+                    if line_datum.children.len() != 1 {
+                        return Err(InputError::new("Malformed line connection").at(line_datum));
+                    }
+                    connections.push((line_datum, &line_datum.children[0]));
+                }
+            } else {
+                return Err(InputError::new(&format!("Unknown include property: {}", property_datum.value)).at(property_datum));
+            }
+        }
+        let file_datum = file_datum.ok_or_else(|| InputError::new("Include with no file").at(datum))?;
+        let path = dir.join(file_datum.value);
+        if including.contains(&path.canonicalize().unwrap_or_else(|_| path.clone())) {
+            return Err(InputError::new(&format!("{} includes itself", file_datum.value)).at(file_datum));
+        }
+        let included = Design::read_file(&path, including).map_err(|e| e.at(file_datum))?;
+        if included.recipe_mode != self.recipe_mode {
+            return Err(InputError::new(&format!("{} has Recipe Mode {:?}, but the including design has Recipe Mode {:?}",
+                                                file_datum.value, included.recipe_mode, self.recipe_mode)).at(datum));
+        }
+        let prefix = match name_datum {
+            Some(name_datum) => name_datum.value.to_string(),
+            None => path.file_stem().map_or_else(String::new, |stem| stem.to_string_lossy().into_owned()),
+        };
+
+        let mut line_names = HashMap::new();
+        for (line_datum, parent_datum) in connections {
+            let is_input = included.input_lines.contains(line_datum.value);
+            let is_output = included.output_lines.contains(line_datum.value);
+            if !is_input && !is_output {
+                return Err(InputError::new(&format!("{} is not an input or output of {}", line_datum.value, file_datum.value)).at(line_datum));
+            }
+            let resource_type = included.resource_lines[line_datum.value].resource_type;
+            self.get_line(resource_type, parent_datum.value).map_err(|e| e.at(parent_datum))?;
+            let position = (parent_datum.line, parent_datum.column);
+            if is_input {
+                consumed_at.entry(parent_datum.value.to_string()).or_insert(position);
+            }
+            if is_output {
+                produced_at.entry(parent_datum.value.to_string()).or_insert(position);
+            }
+            line_names.insert(line_datum.value.to_string(), parent_datum.value.to_string());
+        }
+        for included_line in included.resource_lines.values() {
+            if line_names.contains_key(&included_line.name) {
+                continue;
+            }
+            let line_name = format!("{}/{}", prefix, included_line.name);
+            if self.resource_lines.contains_key(&line_name) {
+                return Err(InputError::new(&format!("Line {} is already in the design; give the include a different Name", line_name)).at(datum));
+            }
+            self.get_line(included_line.resource_type, &line_name).map_err(|e| e.at(datum))?;
+            if included.input_lines.contains(&included_line.name) {
+                self.input_lines.insert(line_name.clone());
+            }
+            if included.output_lines.contains(&included_line.name) {
+                self.output_lines.insert(line_name.clone());
+            }
+            if included.overflow_lines.contains(&included_line.name) {
+                self.overflow_lines.insert(line_name.clone());
+            }
+            line_names.insert(included_line.name.clone(), line_name);
+        }
+
+        for building in included.buildings.iter() {
+            let mut building = building.clone();
+            for &mut (ref mut line, _) in building.recipe.inputs.iter_mut().chain(building.recipe.outputs.iter_mut()) {
+                *line = self.resource_lines[&line_names[&line.name]].clone();
+            }
+            if let Some(ref mut fuel) = building.fuel {
+                *fuel = self.resource_lines[&line_names[&fuel.name]].clone();
+            }
+            building.index = self.next_index;
+            self.next_index += 1;
+            self.buildings.push(building);
+        }
+        // Lengths given in the including design take precedence.
+        for (line_name, &length) in included.pipe_lengths.iter() {
+            self.pipe_lengths.entry(line_names[line_name].clone()).or_insert(length);
+        }
+        for warning in included.warnings {
+            if !self.warnings.contains(&warning) {
+                self.warnings.push(warning);
+            }
+        }
+        Ok(())
+    }
+
+    fn add_building(&mut self, proto_building: &ProtoBuilding, recipe: Recipe, crafting_speed: f32, modules: Vec<(Module, i16)>, beacons: Option<Beacons>, fuel: Option<ResourceLine>) -> &mut Building {
+        let building_index = self.next_index;
+        self.next_index += 1;
+
+        let building = Building {
+            name: proto_building.name,
+            recipe,
+            energy_consumption: proto_building.energy_consumption,
+            drain: proto_building.drain,
+            crafting_speed,
+            modules,
+            module_slots: proto_building.module_slots,
+            beacons,
+            energy_source: proto_building.energy_source,
+            fuel,
+            base_productivity: 0.0,
+            neighbor_bonus: 0.0,
+            accumulators: proto_building.accumulators,
+            pollution: proto_building.pollution,
+            count: None,
+            minimum_count: None,
+            maximum_count: None,
+            allowed_modules: Vec::new(),
+            index: building_index,
+        };
+        self.buildings.push(building);
+        self.buildings.last_mut().unwrap()
+    }
+
+    // Builds the production chain for a design that only lists its inputs,
+    // outputs and targets. Starting from the outputs, every resource that is
+    // not a design input gets one building running the chosen (or default)
+    // recipe for it, in the default building for that recipe's category.
+    // Intermediate lines are named after their resource, and byproducts that
+    // nothing consumes become additional outputs. Burner buildings are fed
+    // the default fuel of the game data.
+    fn plan_buildings(&mut self, recipe_choices: &HashMap<Resource, ProtoRecipe>) -> Result<(), InputError> {
+        let mut input_lines_by_resource = HashMap::new();
+        for input_name in self.input_lines.iter() {
+            let input_line = &self.resource_lines[input_name];
+            input_lines_by_resource.insert(input_line.resource_type, input_line.name.clone());
+        }
+        // Where several outputs carry a resource, the first by name is used.
+        let mut output_names : Vec<&String> = self.output_lines.iter().collect();
+        output_names.sort();
+        let mut output_lines_by_resource = HashMap::new();
+        for output_name in output_names {
+            let output_line = &self.resource_lines[output_name];
+            output_lines_by_resource.entry(output_line.resource_type).or_insert_with(|| output_line.name.clone());
+        }
+
+        // Targets may name a resource instead of a declared line, in which
+        // case they go to the output line for the resource, or to a new
+        // output line named after the resource if there is none.
+        let mut targets = Vec::new();
+        for (target_name, target_val) in self.targets.clone() {
+            if self.resource_lines.contains_key(&target_name) {
+                targets.push((target_name, target_val));
+                continue;
+            }
+            let resource_type = Resource::from_str(&target_name)?;
+            if let Some(line_name) = output_lines_by_resource.get(&resource_type) {
+                targets.push((line_name.clone(), target_val));
+                continue;
+            }
+            let line_name = resource_type.name().to_string();
+            self.get_line(resource_type, &line_name)?;
+            self.output_lines.insert(line_name.clone());
+            output_lines_by_resource.insert(resource_type, line_name.clone());
+            targets.push((line_name, target_val));
+        }
+        self.targets = targets;
+
+        let planned_fuel = Database::current().default_fuel();
+        // Resources are planned in a fixed order, targets first and then the
+        // other outputs by name, so that a design is always planned the same
+        // way. Each pending resource carries the path of resources it is
+        // being planned for.
+        let mut seeds : Vec<Resource> = self.targets.iter()
+            .map(|(line_name, _)| self.resource_lines[line_name].resource_type)
+            .collect();
+        let mut output_names : Vec<&String> = output_lines_by_resource.values().collect();
+        output_names.sort();
+        seeds.extend(output_names.into_iter().map(|name| self.resource_lines[name].resource_type));
+        let mut pending : Vec<(Resource, Vec<Resource>)> = seeds.into_iter().rev().map(|resource| (resource, Vec::new())).collect();
+        let mut deferred = Vec::new();
+        let mut produced = HashSet::new();
+        let mut planned_recipes = Vec::new();
+        loop {
+            // Recipes with several outputs, such as oil processing, are only
+            // planned once nothing else is pending. By then their outputs that
+            // a recipe of their own can make (such as petroleum from cracking)
+            // are planned, so the outputs can be balanced against each other
+            // rather than being fixed by whichever target came first.
+            let (resource, path, settling) = match pending.pop() {
+                Some((resource, path)) => (resource, path, false),
+                None => match deferred.pop() {
+                    Some((resource, path)) => (resource, path, true),
+                    None => break,
+                },
+            };
+            if produced.contains(&resource) || input_lines_by_resource.contains_key(&resource) {
+                continue;
+            }
+            let proto_recipe = match recipe_choices.get(&resource) {
+                Some(proto_recipe) => proto_recipe.clone(),
+                None => self.plannable_recipe(resource, &path, &input_lines_by_resource, recipe_choices)
+                    .map_err(|missing| InputError::new(&format!("No recipe produces {}; add it to the inputs", missing.name())))?,
+            };
+            if proto_recipe.outputs.len() > 1 && !settling {
+                deferred.push((resource, path));
+                continue;
+            }
+            let mut inner_path = path;
+            inner_path.push(resource);
+            for &(output, _) in proto_recipe.outputs.iter() {
+                produced.insert(output);
+            }
+            for &(input, _) in proto_recipe.inputs.iter() {
+                pending.push((input, inner_path.clone()));
+            }
+            if let Some(proto_building) = ProtoBuilding::from_name(proto_recipe.category.default_building) {
+                if proto_building.energy_source != EnergySource::Electric {
+                    let fuel = planned_fuel
+                        .ok_or_else(|| InputError::new(&format!("No default fuel for {}", proto_building.name)))?;
+                    pending.push((fuel, inner_path.clone()));
+                }
+            }
+            planned_recipes.push(proto_recipe);
+        }
+        // Raw materials first, final products last.
+        planned_recipes.reverse();
+
+        let line_name = |resource: Resource| -> String {
+            input_lines_by_resource.get(&resource)
+                .or_else(|| output_lines_by_resource.get(&resource))
+                .cloned()
+                .unwrap_or_else(|| resource.name().to_string())
+        };
+        let mut consumed = HashSet::new();
+        for proto_recipe in planned_recipes {
+            let building_name = proto_recipe.category.default_building;
+            let proto_building = ProtoBuilding::from_name(building_name)
+                .ok_or(InputError::new(&format!("Unknown building: {}", building_name)))?;
+            let mut line_inputs = Vec::new();
+            for &(input, qty) in proto_recipe.inputs.iter() {
+                let resource_line = self.get_line(input, &line_name(input))?;
+                consumed.insert(resource_line.name.clone());
+                line_inputs.push((resource_line, qty));
+            }
+            let mut line_outputs = Vec::new();
+            for &(output, qty) in proto_recipe.outputs.iter() {
+                let resource_line = self.get_line(output, &line_name(output))?;
+                line_outputs.push((resource_line, qty));
+            }
+            let recipe = Recipe {
+                name: proto_recipe.name,
+                inputs: line_inputs,
+                outputs: line_outputs,
+                time: proto_recipe.time,
+                category: proto_recipe.category,
+            };
+            let fuel = if proto_building.energy_source == EnergySource::Electric {
+                None
+            } else {
+                let fuel = planned_fuel.unwrap();
+                let fuel_line = self.get_line(fuel, &line_name(fuel))?;
+                consumed.insert(fuel_line.name.clone());
+                Some(fuel_line)
+            };
+            let crafting_speed = proto_building.crafting_speed_for(&proto_recipe);
+            self.add_building(&proto_building, recipe, crafting_speed, Vec::new(), None, fuel);
+        }
+
+        let byproducts : Vec<String> = self.resource_lines.keys()
+            .filter(|name| !consumed.contains(*name) && !self.input_lines.contains(*name))
+            .cloned()
+            .collect();
+        self.output_lines.extend(byproducts);
+        Ok(())
+    }
+
+    // The first candidate recipe for `resource` whose inputs can all be
+    // planned without going back through `path`, the resources it is being
+    // planned for. Otherwise a fluid missing from the inputs would be planned
+    // as a loop of filling and emptying barrels. When there is none, the
+    // error is the resource that the best candidate cannot get, such as the
+    // fluid the barrels would loop back to.
+    fn plannable_recipe(&self, resource: Resource, path: &[Resource], input_lines_by_resource: &HashMap<Resource, String>,
+                        recipe_choices: &HashMap<Resource, ProtoRecipe>) -> Result<ProtoRecipe, Resource> {
+        let mut inner_path = path.to_vec();
+        inner_path.push(resource);
+        let mut missing = None;
+        for proto_recipe in ProtoRecipe::candidates_for(resource, self.recipe_mode) {
+            let mut blocked = None;
+            for &(input, _) in proto_recipe.inputs.iter() {
+                if input_lines_by_resource.contains_key(&input) || recipe_choices.contains_key(&input) {
+                    continue;
+                }
+                if inner_path.contains(&input) {
+                    blocked = Some(input);
+                } else if let Err(missing_input) = self.plannable_recipe(input, &inner_path, input_lines_by_resource, recipe_choices) {
+                    blocked = Some(missing_input);
+                }
+                if blocked.is_some() {
+                    break;
+                }
+            }
+            match blocked {
+                None => return Ok(proto_recipe),
+                Some(blocked) => {
+                    missing.get_or_insert(blocked);
+                },
+            }
+        }
+        Err(missing.unwrap_or(resource))
+    }
+
+    // How much of each line a single copy of each of `buildings` uses per
+    // second, by line index and then by building, with inputs positive as in
+    // the analysis equations. Electric buildings use the power grid too.
+    pub(crate) fn line_use<'a, I>(&self, buildings: I) -> Vec<Vec<f64>> where I: ExactSizeIterator<Item = &'a Building> {
+        let mut line_use = vec![vec![0.0; buildings.len()]; self.next_index];
+        let grid = self.power_grid();
+        for (column, building) in buildings.enumerate() {
+            for (&line_index, &coefficient) in building.analysis_coefficients().iter() {
+                line_use[line_index][column] += f64::from(coefficient);
+            }
+            if let Some(grid) = grid {
+                let energy = building.electric_energy_consumption() + building.beacon_energy_consumption();
+                line_use[grid.index][column] += f64::from(energy);
+            }
+        }
+        line_use
+    }
+
+    // The smallest surplus each overflow line can be left with, keyed by
+    // line index. Letting a line run over turns its equation into an
+    // inequality, so this is found with a linear program over non-negative
+    // building counts. Analysis then holds the overflow lines at these values
+    // in place of netting them to zero, which keeps its diagnostics for the
+    // rest of the design.
+    fn overflow_surplus(&self) -> Result<HashMap<usize, f64>, AnalyzeError> {
+        let mut surplus = HashMap::new();
+        if self.overflow_lines.is_empty() {
+            return Ok(surplus);
+        }
+        let line_use = self.line_use(self.buildings.iter());
+        let mut program = LinearProgram::new(self.buildings.len());
+        let mut costs = vec![0.0; self.buildings.len()];
+        for line in self.resource_lines.values() {
+            let coefficients = line_use[line.index].clone();
+            if self.overflow_lines.contains(&line.name) {
+                // The surplus is what the line nets, so its cost is minus
+                // its use.
+                for (cost, &used) in costs.iter_mut().zip(coefficients.iter()) {
+                    *cost -= used;
+                }
+                program.add_constraint(coefficients, Relation::AtMost, 0.0);
+            } else if !self.input_lines.contains(&line.name) && !self.output_lines.contains(&line.name) {
+                program.add_constraint(coefficients, Relation::Equal, 0.0);
+            }
+        }
+        let mut has_fixed_count = false;
+        for (position, building) in self.buildings.iter().enumerate() {
+            if let Some(count) = building.count {
+                let mut coefficients = vec![0.0; self.buildings.len()];
+                coefficients[position] = 1.0;
+                program.add_constraint(coefficients, Relation::Equal, f64::from(count));
+                has_fixed_count = true;
+            }
+        }
+        // Missing lines are reported by the analysis itself.
+        if self.targets.is_empty() && !has_fixed_count {
+            if let Some(first_output) = self.output_lines.iter().nth(0).and_then(|name| self.resource_lines.get(name)) {
+                program.add_constraint(line_use[first_output.index].clone(), Relation::Equal, -1.0);
+            }
+        } else {
+            for &(ref line_name, target_val) in self.targets.iter() {
+                if let Some(target_line) = self.resource_lines.get(line_name) {
+                    program.add_constraint(line_use[target_line.index].clone(), Relation::Equal, -f64::from(target_val));
+                }
+            }
+        }
+
+        let counts = program.minimize(&costs).map_err(|_| AnalyzeError::new(
+            "No building counts meet the targets without running an overflow line short; add an input, or a building that makes what is missing"))?;
+        for line in self.resource_lines.values() {
+            if self.overflow_lines.contains(&line.name) {
+                let used : f64 = line_use[line.index].iter().zip(counts.iter()).map(|(used, count)| used * count).sum();
+                surplus.insert(line.index, if -used > MIN_SURPLUS { -used } else { 0.0 });
+            }
+        }
+        Ok(surplus)
+    }
+
+    pub fn analyze(&self) -> Result<Analysis, AnalyzeError> {
+        // If the specified design is fully specified, then there will be one
+        // set of nonzero rates (up to scalar factors) that determines how fast
+        // each of the parts is working. The vector returned in that case will
+        // have the following values:
+        //
+        // For each building, how many copies of that building are operating
+        // For each resource line, how much of that resource is netted per second
+        //
+        // The location of these values is defined by the `index` attribute of
+        // each building and resource line, and they are collected into an
+        // `Analysis` once solved.
+        //
+        // To compute these, we build up a system of linear equations. There are
+        // two types of equations:
+        //
+        // 1) Each resource line variable is the sum of all contributions of attached
+        //    buildings (positive contribution for outputs, negative for inputs).
+        // 2) For each resource line that is not an input or output, that resource line
+        //    must net 0. Overflow lines instead net the smallest surplus they can.
+        //
+        // If the design has a power grid line, every building also consumes its
+        // electric energy (and that of its beacons) from the grid, so generators
+        // are sized to run the rest of the design.
+        let num_variables = self.next_index;
+        let mut system = LinearSystem::new(num_variables);
+        // A description of each equation, for explaining failures.
+        let mut constraints = Vec::new();
+
+        // Main balance equations
+        let mut io_equations : HashMap<usize, Vec<f64>> = HashMap::new();
+        for line in self.resource_lines.values() {
+            let mut equation = vec![0.0; num_variables];
+            equation[line.index] = 1.0;
+            io_equations.insert(line.index, equation);
+        }
+        for building in self.buildings.iter() {
+            let coefficients = building.analysis_coefficients();
+            for (&line_index, &line_coeff) in coefficients.iter() {
+                if let Some(eq) = io_equations.get_mut(&line_index) {
+                    eq[building.index] = f64::from(line_coeff);
+                }
+            }
+        }
+        if let Some(grid) = self.power_grid() {
+            let eq = io_equations.get_mut(&grid.index).unwrap();
+            for building in self.buildings.iter() {
+                let energy = building.electric_energy_consumption() + building.beacon_energy_consumption();
+                eq[building.index] += f64::from(energy);
+            }
+        }
+        for line in self.resource_lines.values() {
+            system.add_equation(io_equations.remove(&line.index).unwrap(), 0.0);
+            constraints.push(format!("line {} carries what its buildings produce and consume", line.name));
+        }
+
+        // Equations to force all non-input/output lines to 0, or overflow
+        // lines to their smallest surplus
+        let surplus = self.overflow_surplus()?;
+        for line in self.resource_lines.values() {
+            if !self.input_lines.contains(&line.name) && !self.output_lines.contains(&line.name) {
+                let mut equation = vec![0.0; num_variables];
+                equation[line.index] = 1.0;
+                match surplus.get(&line.index) {
+                    Some(&value) => {
+                        system.add_equation(equation, value);
+                        constraints.push(format!("overflow line {} has its smallest possible surplus, {}", line.name, value));
+                    },
+                    None => {
+                        system.add_equation(equation, 0.0);
+                        constraints.push(format!("line {} must net zero", line.name));
+                    },
+                }
+            }
+        }
+
+        // Buildings with a fixed count set the scale of the design on their
+        // own, so they take the place of the default normalization.
+        let mut has_fixed_count = false;
+        for (position, building) in self.buildings.iter().enumerate() {
+            if let Some(count) = building.count {
+                let mut equation = vec![0.0; num_variables];
+                equation[building.index] = 1.0;
+                system.add_equation(equation, f64::from(count));
+                constraints.push(format!("building {}: {} ({}) has a fixed count of {}", position + 1, building.name, building.recipe.name, count));
+                has_fixed_count = true;
+            }
+        }
+
+        // Equations to normalize the result
+        if self.targets.is_empty() && !has_fixed_count {
+            // Default normalization sets first output to 1.0
+            let first_output_name = self.output_lines.iter().nth(0).ok_or(AnalyzeError::new("No outputs to normalize!"))?;
+            let first_output_line = self.resource_lines.get(first_output_name)
+                .ok_or(AnalyzeError::new("No line for first output"))?;
+            let mut norm_eq = vec![0.0; num_variables];
+            norm_eq[first_output_line.index] = 1.0;
+            system.add_equation(norm_eq, 1.0);
+            constraints.push(format!("output {} is normalized to 1 (no targets given)", first_output_name));
+        } else {
+            for &(ref line_name, target_val) in self.targets.iter() {
+                let target_line = self.resource_lines.get(line_name)
+                    .ok_or_else(|| AnalyzeError::new(&format!("No such target line: {}", line_name)))?;
+                let mut norm_eq = vec![0.0; num_variables];
+                norm_eq[target_line.index] = 1.0;
+                system.add_equation(norm_eq, f64::from(target_val));
+                constraints.push(format!("target {}: {}", line_name, target_val));
+            }
+        }
+
+        match system.solve() {
+            Ok(solution) => {
+                self.check_signs(&system, &constraints, &solution)?;
+                let solution : Vec<f32> = solution.into_iter().map(|x| x as f32).collect();
+                self.check_count_bounds(&solution)?;
+                Ok(Analysis::from_solution(self, &solution))
+            },
+            Err(SystemError::Underdetermined(directions)) => {
+                let mut message = String::from("Design is under-determined.");
+                for direction in directions {
+                    message.push_str("\nThese can change together without breaking any constraint:");
+                    for (variable, _) in direction {
+                        message.push_str(&format!("\n    {}", self.variable_name(variable)));
+                    }
+                }
+                message.push_str("\nAdd a target, fix the Count of a building, or connect their lines to the rest of the design.");
+                Err(AnalyzeError::new(&message))
+            },
+            Err(SystemError::Inconsistent(equations)) => {
+                let mut message = String::from("Design is over-determined. These constraints cannot all hold:");
+                for equation in equations {
+                    message.push_str(&format!("\n    {}", constraints[equation]));
+                }
+                message.push_str("\nRemove a target or fixed Count, or mark one of the lines as an input or output.");
+                Err(AnalyzeError::new(&message))
+            },
+        }
+    }
+
+    // A solution can run buildings backwards, or take from an output and put
+    // into an input, when lines are wired to the wrong buildings or recipes
+    // are mixed in the wrong ratio. Each such value is explained by how much
+    // every target, count and normalization adds to it.
+    fn check_signs(&self, system: &LinearSystem, constraints: &[String], solution: &[f64]) -> Result<(), AnalyzeError> {
+        let tolerance = SIGN_TOLERANCE * solution.iter().fold(1.0f64, |m, x| m.max(x.abs()));
+        let mut violations = Vec::new();
+        for building in self.buildings.iter() {
+            let count = solution[building.index];
+            if count < -tolerance {
+                violations.push((building.index, format!("{} has a negative count of {}", self.variable_name(building.index), count as f32)));
+            }
+        }
+        for line in self.resource_lines.values() {
+            let rate = solution[line.index];
+            if self.input_lines.contains(&line.name) && rate > tolerance {
+                violations.push((line.index, format!("input line {} produces {} per sec instead of consuming", line.name, rate as f32)));
+            }
+            if self.output_lines.contains(&line.name) && rate < -tolerance {
+                violations.push((line.index, format!("output line {} consumes {} per sec instead of producing", line.name, -rate as f32)));
+            }
+        }
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let mut message = String::from("Design solves with rates of the wrong sign:");
+        for (variable, violation) in violations {
+            message.push_str(&format!("\n    {}", violation));
+            for (equation, contribution) in system.contributions(variable) {
+                message.push_str(&format!("\n        {} from {}", contribution as f32, constraints[equation]));
+            }
+        }
+        message.push_str("\nCheck that the lines of these buildings are connected as intended, or mark a line as an input, output or overflow.");
+        Err(AnalyzeError::new(&message))
+    }
+
+    // Bounds on building counts cannot steer the solution, which is unique,
+    // so they are checked once it is known.
+    fn check_count_bounds(&self, solution: &[f32]) -> Result<(), AnalyzeError> {
+        let mut violations = Vec::new();
+        for building in self.buildings.iter() {
+            let count = solution[building.index];
+            if let Some(minimum) = building.minimum_count {
+                if count < minimum * (1.0 - COUNT_TOLERANCE) {
+                    violations.push(format!("{} needs {}, fewer than its minimum of {}", self.variable_name(building.index), count, minimum));
+                }
+            }
+            if let Some(maximum) = building.maximum_count {
+                if count > maximum * (1.0 + COUNT_TOLERANCE) {
+                    violations.push(format!("{} needs {}, more than its maximum of {}", self.variable_name(building.index), count, maximum));
+                }
+            }
+        }
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let mut message = String::from("Building counts are out of bounds:");
+        for violation in violations {
+            message.push_str(&format!("\n    {}", violation));
+        }
+        message.push_str("\nChange the targets, or fix the Count of the limiting building to see what it can supply.");
+        Err(AnalyzeError::new(&message))
+    }
+
+    // Describes a variable of the design's system of equations.
+    fn variable_name(&self, index: usize) -> String {
+        for (position, building) in self.buildings.iter().enumerate() {
+            if building.index == index {
+                return format!("building {}: {} ({})", position + 1, building.name, building.recipe.name);
+            }
+        }
+        for line in self.resource_lines.values() {
+            if line.index == index {
+                return format!("line {}", line.name);
+            }
+        }
+        format!("variable {}", index)
+    }
+
+    pub fn print_results(&self, analysis: &Analysis) {
+        self.print_report(analysis, false);
+    }
+
+    // Prints the results with building counts rounded up to whole machines,
+    // how busy those machines are, and how much to scale the design by for
+    // every count to come out whole.
+    pub fn print_rounded_results(&self, analysis: &Analysis) {
+        self.print_report(analysis, true);
+        println!();
+        match analysis.whole_multiplier(MAX_WHOLE_MULTIPLIER) {
+            Some(multiplier) => {
+                println!("Whole-number ratio: {}x the design", multiplier);
+                for output_name in self.output_lines.iter() {
+                    println!("    {}: {}", output_name, self.format_rate(output_name, analysis.line_rates[output_name] * multiplier as f32));
+                }
+                for (building, building_analysis) in self.buildings.iter().zip(analysis.buildings.iter()) {
+                    println!("    {} ({}): {}", building.name, building.recipe.name, (building_analysis.count * multiplier as f32).round());
+                }
+            },
+            None => println!("No multiplier up to {} gives whole-number building counts", MAX_WHOLE_MULTIPLIER),
+        }
+    }
+
+    fn print_report(&self, analysis: &Analysis, rounded: bool) {
+        println!("Inputs:");
+        for input_name in self.input_lines.iter() {
+            let input_line = self.resource_lines.get(input_name).unwrap();
+            println!("    {}: {}", input_line.name, self.format_rate(input_name, -analysis.line_rates[input_name]));
+        }
+        println!();
+        println!("Outputs:");
+        for output_name in self.output_lines.iter() {
+            let output_line = self.resource_lines.get(output_name).unwrap();
+            println!("    {}: {}", output_line.name, self.format_rate(output_name, analysis.line_rates[output_name]));
+        }
+        if !self.overflow_lines.is_empty() {
+            println!();
+            println!("Overflow:");
+            for overflow_name in self.overflow_lines.iter() {
+                println!("    {}: {}", overflow_name, self.format_rate(overflow_name, analysis.line_rates[overflow_name]));
+            }
+        }
+        for (building, building_analysis) in self.buildings.iter().zip(analysis.buildings.iter()) {
+            println!();
+            println!("{}", building.name);
+            println!("    {}", building.recipe.name);
+            println!("    Modules:");
+            for &(module_type, module_count) in building.modules.iter() {
+                println!("        {}: {}", module_type.display_name(), module_count);
+            }
+            if let Some(ref beacons) = building.beacons {
+                if beacons.shared_by == 1.0 {
+                    println!("    Beacons: {}", beacons.count);
+                } else {
+                    println!("    Beacons: {}, each shared by {}", beacons.count, beacons.shared_by);
+                }
+                for &(module_type, module_count) in beacons.modules.iter() {
+                    println!("        {}: {}", module_type.display_name(), module_count);
+                }
+            }
+            let fixed = if building.count.is_some() { "fixed, " } else { "" };
+            if rounded {
+                println!("    Count: {} ({}{:.1}% utilized)", building_analysis.whole_count(), fixed, building_analysis.utilization() * 100.0);
+            } else if building.count.is_some() {
+                println!("    Count: {} (fixed)", building_analysis.count);
+            } else {
+                println!("    Count: {}", building_analysis.count);
+            }
+            match building.fuel {
+                Some(ref fuel_line) => println!("    Fuel: {}", fuel_line.resource_type.name()),
+                None => println!("    Energy cost: {} kW", building_analysis.energy),
+            }
+            if building.beacons.is_some() {
+                println!("    Beacon energy cost: {} kW", building_analysis.beacon_energy);
+            }
+            if building_analysis.accumulators > 0.0 {
+                println!("    Accumulators: {}", building_analysis.accumulators);
+            }
+
+            println!("    Inputs:");
+            for &(ref input_name, input_rate) in building_analysis.inputs.iter() {
+                println!("        {}: {}", input_name, self.format_rate(input_name, input_rate));
+            }
+
+            println!("    Outputs:");
+            for &(ref output_name, output_rate) in building_analysis.outputs.iter() {
+                println!("        {}: {}", output_name, self.format_rate(output_name, output_rate));
+            }
+        }
+        println!();
+        if let Some(objective) = self.objective {
+            println!("Minimized {}: {}", objective.name(), objective.value(self, analysis));
+        }
+        println!("Total energy cost: {} kW", analysis.total_energy);
+        if self.power_grid().is_some() {
+            println!("Total power production: {} kW", analysis.power_production);
+            println!("Power surplus: {} kW", analysis.power_production - analysis.total_energy);
+        }
+    }
+
+    // The results as JSON, for scripts to read. Rates are per second, with
+    // inputs positive, and energy is in kW.
+    pub fn results_json(&self, analysis: &Analysis) -> Value {
+        let line_rates = |names: &HashSet<String>, sign: f32| -> Map<String, Value> {
+            names.iter()
+                .map(|name| (name.clone(), json_number(sign * analysis.line_rates[name])))
+                .collect()
+        };
+        let mut buildings = Vec::new();
+        for (building, building_analysis) in self.buildings.iter().zip(analysis.buildings.iter()) {
+            let modules = |modules: &[(Module, i16)]| -> Map<String, Value> {
+                modules.iter()
+                    .map(|&(module, count)| (module.display_name().to_string(), Value::from(count)))
+                    .collect()
+            };
+            let building_rates = |rates: &[(String, f32)]| -> Map<String, Value> {
+                let mut totals : Vec<(&str, f32)> = Vec::new();
+                for &(ref name, rate) in rates.iter() {
+                    match totals.iter_mut().find(|(total_name, _)| total_name == name) {
+                        Some(total) => total.1 += rate,
+                        None => totals.push((name, rate)),
+                    }
+                }
+                totals.into_iter().map(|(name, rate)| (name.to_string(), json_number(rate))).collect()
+            };
+            let beacons = building.beacons.as_ref().map_or(Value::Null, |beacons| json!({
+                "count": beacons.count,
+                "shared_by": json_number(beacons.shared_by),
+                "modules": modules(&beacons.modules),
+                "energy": json_number(building_analysis.beacon_energy),
+            }));
+            buildings.push(json!({
+                "name": building.name,
+                "recipe": building.recipe.name,
+                "count": json_number(building_analysis.count),
+                "fixed": building.count.is_some(),
+                "whole_count": json_number(building_analysis.whole_count()),
+                "utilization": json_number(building_analysis.utilization()),
+                "modules": modules(&building.modules),
+                "beacons": beacons,
+                "fuel": building.fuel.as_ref().map(|fuel_line| fuel_line.resource_type.name()),
+                "energy": json_number(building_analysis.energy),
+                "accumulators": json_number(building_analysis.accumulators),
+                "pollution": json_number(building_analysis.pollution),
+                "inputs": building_rates(&building_analysis.inputs),
+                "outputs": building_rates(&building_analysis.outputs),
+            }));
+        }
+        let mut results = json!({
+            "inputs": line_rates(&self.input_lines, -1.0),
+            "outputs": line_rates(&self.output_lines, 1.0),
+            "overflow": line_rates(&self.overflow_lines, 1.0),
+            "buildings": buildings,
+            "total_energy": json_number(analysis.total_energy),
+        });
+        if self.power_grid().is_some() {
+            results["power_production"] = json_number(analysis.power_production);
+        }
+        if let Some(objective) = self.objective {
+            results["objective"] = json!({
+                "name": objective.name(),
+                "value": json_number(objective.value(self, analysis)),
+            });
+        }
+        results
+    }
+
+    pub fn print_json_results(&self, analysis: &Analysis) {
+        println!("{}", serde_json::to_string_pretty(&self.results_json(analysis)).unwrap());
+    }
+
+    // The design as a Graphviz graph. Buildings are boxes labeled with their
+    // recipe and count, and lines are ellipses labeled with how much flows
+    // through them, with edges for what each building takes and gives.
+    // Inputs, outputs and overflow lines are filled in. Electric buildings
+    // are not joined to the power grid, which would tie the graph together.
+    pub fn results_dot(&self, analysis: &Analysis) -> String {
+        let mut throughputs : HashMap<&str, (f32, f32)> = HashMap::new();
+        for building_analysis in analysis.buildings.iter() {
+            for &(ref name, rate) in building_analysis.inputs.iter() {
+                throughputs.entry(name).or_insert((0.0, 0.0)).0 += rate;
+            }
+            for &(ref name, rate) in building_analysis.outputs.iter() {
+                throughputs.entry(name).or_insert((0.0, 0.0)).1 += rate;
+            }
+        }
+
+        let mut dot = String::from("digraph design {\n    rankdir=LR;\n");
+        let mut line_names : Vec<&String> = self.resource_lines.keys().collect();
+        line_names.sort();
+        for line_name in line_names {
+            let (consumed, produced) = throughputs.get(&line_name[..]).cloned().unwrap_or((0.0, 0.0));
+            let label = format!("{}\n{}", line_name, self.format_rate(line_name, consumed.max(produced)));
+            let fill = if self.input_lines.contains(line_name) {
+                ", style=filled, fillcolor=lightblue"
+            } else if self.output_lines.contains(line_name) {
+                ", style=filled, fillcolor=palegreen"
+            } else if self.overflow_lines.contains(line_name) {
+                ", style=filled, fillcolor=orange"
+            } else {
+                ""
+            };
+            dot.push_str(&format!("    \"line:{}\" [shape=ellipse, label=\"{}\"{}];\n", dot_escape(line_name), dot_escape(&label), fill));
+        }
+        for (position, (building, building_analysis)) in self.buildings.iter().zip(analysis.buildings.iter()).enumerate() {
+            let label = format!("{}\n{}\nCount: {}", building.name, building.recipe.name, building_analysis.count);
+            dot.push_str(&format!("    \"building:{}\" [shape=box, label=\"{}\"];\n", position, dot_escape(&label)));
+            for &(ref name, rate) in building_analysis.inputs.iter() {
+                dot.push_str(&format!("    \"line:{}\" -> \"building:{}\" [label=\"{}\"];\n",
+                                      dot_escape(name), position, dot_escape(&self.format_rate(name, rate))));
+            }
+            for &(ref name, rate) in building_analysis.outputs.iter() {
+                dot.push_str(&format!("    \"building:{}\" -> \"line:{}\" [label=\"{}\"];\n",
+                                      position, dot_escape(name), dot_escape(&self.format_rate(name, rate))));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn print_dot_results(&self, analysis: &Analysis) {
+        print!("{}", self.results_dot(analysis));
+    }
+
+    // Prints how many belts or pipes each line needs, and how many inserters
+    // each machine needs to load and unload its items.
+    pub fn print_logistics(&self, analysis: &Analysis) {
+        // Lines carry what is put onto them, or what is taken off them if
+        // that is more, as for input lines.
+        let mut produced : HashMap<&str, f32> = HashMap::new();
+        let mut consumed : HashMap<&str, f32> = HashMap::new();
+        for building_analysis in analysis.buildings.iter() {
+            for &(ref line_name, rate) in building_analysis.outputs.iter() {
+                *produced.entry(line_name).or_insert(0.0) += rate;
+            }
+            for &(ref line_name, rate) in building_analysis.inputs.iter() {
+                *consumed.entry(line_name).or_insert(0.0) += rate;
+            }
+        }
+        let mut lines : Vec<&ResourceLine> = self.resource_lines.values()
+            .filter(|line| !line.resource_type.is_energy())
+            .collect();
+        lines.sort_by(|a, b| a.name.cmp(&b.name));
+
+        println!();
+        println!("Logistics:");
+        for line in lines {
+            let line_name = &line.name[..];
+            let rate = produced.get(line_name).cloned().unwrap_or(0.0).max(consumed.get(line_name).cloned().unwrap_or(0.0));
+            println!("    {} ({}): {}", line.name, line.resource_type.name(), self.format_rate(line_name, rate));
+            if line.resource_type.is_fluid() {
+                let length = self.pipe_lengths.get(line_name).cloned().unwrap_or(DEFAULT_PIPE_LENGTH);
+                let throughput = pipe_throughput(length);
+                println!("        Pipes of {} segments without pumps: {} ({} per sec each)", length, (rate / throughput).ceil(), throughput);
+                let pipelines = (rate / PUMP_THROUGHPUT).ceil();
+                let pumps_per_pipeline = (length as f32 / PUMP_SPACING as f32).ceil();
+                println!("        Pumped pipes: {}", pipelines);
+                println!("        Pumps: {}", pipelines * pumps_per_pipeline);
+            } else {
+                for belt in BELTS.iter() {
+                    let belts = rate / belt.items_per_second;
+                    println!("        {}: {} belts, {} lanes", belt.name, belts, belts * 2.0);
+                }
+            }
+        }
+
+        for (building, building_analysis) in self.buildings.iter().zip(analysis.buildings.iter()) {
+            if building_analysis.count <= 0.0 {
+                continue;
+            }
+            println!();
+            println!("    {} ({}), per machine:", building.name, building.recipe.name);
+            for &(label, rates) in [("Inputs", &building_analysis.inputs), ("Outputs", &building_analysis.outputs)].iter() {
+                let item_rate : f32 = rates.iter()
+                    .filter(|&(line_name, _)| {
+                        let resource_type = self.resource_lines[line_name].resource_type;
+                        !resource_type.is_fluid() && !resource_type.is_energy()
+                    })
+                    .map(|&(_, rate)| rate / building_analysis.count)
+                    .sum();
+                if item_rate == 0.0 {
+                    continue;
+                }
+                println!("        {}: {} items per sec", label, item_rate);
+                for inserter in INSERTERS.iter() {
+                    let needed = (item_rate / inserter.items_per_second(&self.research)).ceil();
+                    if needed > INSERTERS_PER_SIDE as f32 {
+                        println!("            {}: {} (more than fit on one side)", inserter.name, needed);
+                    } else {
+                        println!("            {}: {}", inserter.name, needed);
+                    }
+                }
+                let fastest = INSERTERS.iter()
+                    .map(|inserter| inserter.items_per_second(&self.research))
+                    .fold(0.0, f32::max);
+                if item_rate > fastest * INSERTERS_PER_SIDE as f32 {
+                    println!("            Warning: one side of the machine cannot keep up, even with {} stack inserters", INSERTERS_PER_SIDE);
+                }
+            }
+        }
+    }
+
+    // Rates of energy lines are in kW rather than per second.
+    fn format_rate(&self, line_name: &str, rate: f32) -> String {
+        match self.resource_lines.get(line_name) {
+            Some(line) if line.resource_type.is_energy() => format!("{} kW", rate),
+            _ => format!("{} per sec", rate),
+        }
+    }
+}
+
+// The error for a recipe name that is not in the game data, which says how
+// to write recipes that have been replaced.
+fn unknown_recipe(recipe_name: &str) -> InputError {
+    match FUELED_RECIPES.iter().find(|&&(old_name, _, _)| old_name.eq_ignore_ascii_case(recipe_name)) {
+        Some(&(old_name, new_name, fuel)) => InputError::new(&format!(
+            "Recipe {} is now {}; list its {} line under Fuel instead of Inputs", old_name, new_name, fuel)),
+        None => InputError::new(&format!("Unknown recipe: {}", recipe_name)),
+    }
+}
+
+// Numbers are written with the digits of the f32 rather than of its exact
+// value as an f64, so 0.1 does not come out as 0.10000000149011612.
+fn json_number(x: f32) -> Value {
+    x.to_string().parse::<f64>().ok().and_then(Number::from_f64).map_or(Value::Null, Value::Number)
+}
+
+// Quotes a string for use inside double quotes in a DOT file, where line
+// breaks in labels are written as \n.
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn parse_modules(datum: &Data) -> Result<Vec<(Module, i16)>, InputError> {
+    let mut modules = Vec::new();
+    for module_datum in datum.children.iter() {
+        let module_type = Module::from_name(module_datum.value).map_err(|e| e.at(module_datum))?;
+
+        let module_count : i16;
+        if module_datum.children.is_empty() {
+            module_count = 1;
+        } else if module_datum.children.len() == 1 {
+            module_count = match module_datum.children[0].value.parse() {
+                Ok(n) if n > 0 => n,
+                _ => return Err(InputError::new("Invalid module count").at(&module_datum.children[0])),
+            };
+        } else {
+            return Err(InputError::new("Invalid module count").at(module_datum));
+        }
+
+        modules.push((module_type, module_count));
+    }
+    Ok(modules)
+}
+
+// Productivity modules only work on recipes for intermediate products.
+fn check_productivity(datum: &Data, modules: &[(Module, i16)], proto_recipe: &ProtoRecipe) -> Result<(), InputError> {
+    if proto_recipe.productivity {
+        return Ok(());
+    }
+    for (module_datum, &(module_type, _)) in datum.children.iter().zip(modules.iter()) {
+        if module_type.is_productivity() {
+            return Err(InputError::new(&format!("Productivity modules cannot be used on {}", proto_recipe.name)).at(module_datum));
+        }
+    }
+    Ok(())
+}
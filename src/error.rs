@@ -0,0 +1,126 @@
+use std;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use parse::Data;
+
+#[derive(Debug, Clone)]
+pub struct InputError {
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+// Where in a design file an input error was found.
+#[derive(Debug, Clone)]
+pub struct Location {
+    pub file: Option<String>,
+    // Line and column of the offending text, counting from 1.
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    // The full line of the file containing the offending text.
+    pub source_line: Option<String>,
+}
+
+impl From<std::str::Utf8Error> for InputError {
+    fn from(_ : std::str::Utf8Error) -> InputError {
+        InputError::new("Unicode error")
+    }
+}
+
+impl InputError {
+    pub fn new(msg: &str) -> InputError {
+        InputError {
+            message: String::from_str(msg).unwrap(),
+            location: None,
+        }
+    }
+
+    // Points the error at a piece of the design, unless a more precise
+    // location is already known.
+    pub(crate) fn at(self, datum: &Data) -> InputError {
+        self.at_position(datum.line, datum.column, datum.value)
+    }
+
+    pub(crate) fn at_position(mut self, line: usize, column: usize, text: &str) -> InputError {
+        if self.location.is_none() {
+            self.location = Some(Location {
+                file: None,
+                line,
+                column,
+                text: text.to_string(),
+                source_line: None,
+            });
+        }
+        self
+    }
+
+    // Fills in the parts of the location that are only known once the
+    // whole file is at hand. Errors from an included file already have them.
+    pub(crate) fn in_source(mut self, contents: &[u8]) -> InputError {
+        if let Some(ref mut location) = self.location {
+            if location.source_line.is_some() {
+                return self;
+            }
+            let line = contents.split(|&b| b == b'\n').nth(location.line - 1);
+            location.source_line = line.map(|line| String::from_utf8_lossy(line).trim_end().to_string());
+        }
+        self
+    }
+
+    pub(crate) fn in_file(mut self, file: &str) -> InputError {
+        if let Some(ref mut location) = self.location {
+            location.file.get_or_insert_with(|| file.to_string());
+        }
+        self
+    }
+}
+
+impl fmt::Display for InputError {
+    // Errors with a location are printed in the style of a compiler
+    // diagnostic, underlining the offending text.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "error: {}", self.message)?;
+        let location = match self.location {
+            Some(ref location) => location,
+            None => return Ok(()),
+        };
+        let line_number = location.line.to_string();
+        let margin = " ".repeat(line_number.len());
+        writeln!(f, "{}--> {}:{}:{}", margin, location.file.as_ref().map_or("<input>", |file| &file[..]), location.line, location.column)?;
+        if let Some(ref source_line) = location.source_line {
+            writeln!(f, "{} |", margin)?;
+            writeln!(f, "{} | {}", line_number, source_line)?;
+            let indent : String = source_line.chars()
+                .take(location.column - 1)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            writeln!(f, "{} | {}{}", margin, indent, "^".repeat(location.text.chars().count().max(1)))?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for InputError {}
+
+#[derive(Debug, Clone)]
+pub struct AnalyzeError {
+    pub message: String,
+}
+
+impl AnalyzeError {
+    pub fn new(msg: &str) -> AnalyzeError {
+        AnalyzeError {
+            message: String::from_str(msg).unwrap(),
+        }
+    }
+}
+
+impl fmt::Display for AnalyzeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Analysis error: {}", self.message)
+    }
+}
+
+impl Error for AnalyzeError {}
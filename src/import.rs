@@ -0,0 +1,406 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde_json::{self, Map, Value};
+
+use building::{EnergySource, ProtoBuilding};
+use database::{intern, Database};
+use error::InputError;
+use recipe::{ProtoRecipe, RecipeCategory, RecipeCost};
+use resource::{ProtoResource, Resource};
+
+// Prototype types of buildings that run recipes.
+const CRAFTING_MACHINE_TYPES : &[&str] = &["assembling-machine", "furnace", "rocket-silo"];
+
+// Crafting machines without a drain given in the data draw 1/30 of their
+// energy use while idle. Other buildings have no drain unless one is given.
+const DEFAULT_DRAIN_DIVISOR : f32 = 30.0;
+
+// Recipes and resources without these use the game's defaults.
+const DEFAULT_RECIPE_CATEGORY : &str = "crafting";
+const DEFAULT_RECIPE_TIME : f32 = 0.5;
+const DEFAULT_RESOURCE_CATEGORY : &str = "basic-solid";
+
+// Mining recipes that need a fluid use this many units for every 10 mined.
+const FLUID_AMOUNT_PER : f32 = 10.0;
+
+// A building that can run recipes, before its categories are known.
+struct Machine<'a> {
+    proto: ProtoBuilding,
+    category_ids: Vec<&'a str>,
+}
+
+impl Database {
+    // Builds a database from a JSON dump of the game's `data.raw` table.
+    // Recipes with their expensive variants, crafting machines, mining
+    // drills and the resources they mine, item fuel values and the recipes
+    // productivity modules are limited to are imported. Power generation is
+    // not.
+    //
+    // Names are made from the ids used in the data, so "iron-plate" becomes
+    // "Iron Plate", with the id kept as an alias of resources and recipes.
+    pub fn from_data_raw(json: &[u8]) -> Result<Database, InputError> {
+        let value : Value = serde_json::from_slice(json)
+            .map_err(|e| InputError::new(&format!("Invalid JSON: {}", e)))?;
+        let raw = value.get("raw").unwrap_or(&value).as_object()
+            .ok_or_else(|| InputError::new("Expected data.raw to be an object"))?;
+        let imported = Importer::new(raw).import()?;
+        // The result is read back from the data file format, so that it gets
+        // the same checks as any data file, such as for duplicate names.
+        Database::parse(imported.to_string().as_bytes())
+    }
+}
+
+struct Importer<'a> {
+    raw: &'a Map<String, Value>,
+    resources: Vec<ProtoResource>,
+    resource_ids: HashMap<&'a str, Resource>,
+    // The mining hardness of resources mined from fields, and whether the
+    // fields are infinite.
+    mined: HashMap<&'a str, (f32, bool)>,
+}
+
+impl<'a> Importer<'a> {
+    fn new(raw: &'a Map<String, Value>) -> Importer<'a> {
+        Importer {
+            raw,
+            resources: Vec::new(),
+            resource_ids: HashMap::new(),
+            mined: HashMap::new(),
+        }
+    }
+
+    fn import(mut self) -> Result<Database, InputError> {
+        // Resources are made with their mining properties, so what each
+        // resource field yields is known before anything else.
+        for (_, prototype) in self.prototypes("resource") {
+            let minable = match prototype.get("minable").and_then(Value::as_object) {
+                Some(minable) => minable,
+                None => continue,
+            };
+            let hardness = minable.get("hardness").and_then(Value::as_f64).unwrap_or(0.0) as f32;
+            let infinite = prototype.get("infinite").and_then(Value::as_bool).unwrap_or(false);
+            let results = minable.get("results").and_then(Value::as_array).map_or(&[][..], |results| &results[..]);
+            let mined_ids = results.iter()
+                .filter_map(|result| result.get("name").or_else(|| result.get(0)).and_then(Value::as_str))
+                .chain(minable.get("result").and_then(Value::as_str));
+            for mined_id in mined_ids {
+                self.mined.insert(mined_id, (hardness, infinite));
+            }
+        }
+
+        // Items that can be burned are imported even if no recipe uses
+        // them, so that the default fuel is known.
+        let raw = self.raw;
+        for prototypes in raw.values().filter_map(Value::as_object) {
+            for (id, prototype) in prototypes.iter() {
+                if prototype.get("stack_size").is_some() && prototype.get("fuel_value").is_some() {
+                    self.resource(id);
+                }
+            }
+        }
+
+        let mut machines = Vec::new();
+        for &machine_type in CRAFTING_MACHINE_TYPES.iter() {
+            for (id, prototype) in self.prototypes(machine_type) {
+                machines.push(Machine {
+                    proto: building(id, prototype, "crafting_speed", true)?,
+                    category_ids: string_list(prototype, "crafting_categories"),
+                });
+            }
+        }
+        let mut mining_category_ids = HashSet::new();
+        for (id, prototype) in self.prototypes("mining-drill") {
+            let mut proto = building(id, prototype, "mining_speed", false)?;
+            proto.mining_power = prototype.get("mining_power").and_then(Value::as_f64).unwrap_or(0.0) as f32;
+            let category_ids = string_list(prototype, "resource_categories");
+            mining_category_ids.extend(category_ids.iter().cloned());
+            machines.push(Machine {
+                proto,
+                category_ids,
+            });
+        }
+
+        // Each category is planned in the fastest electric building that
+        // can run it, or the fastest burner building if there is none.
+        let mut categories : BTreeMap<&str, RecipeCategory> = BTreeMap::new();
+        for machine in machines.iter() {
+            for &category_id in machine.category_ids.iter() {
+                let better = match categories.get(category_id) {
+                    Some(category) => {
+                        let current = machines.iter().find(|m| m.proto.name == category.default_building).unwrap();
+                        planning_rank(&machine.proto) > planning_rank(&current.proto)
+                    },
+                    None => true,
+                };
+                if better {
+                    categories.insert(category_id, RecipeCategory {
+                        name: intern(&display_name(category_id)),
+                        default_building: machine.proto.name,
+                        mining: mining_category_ids.contains(category_id),
+                    });
+                }
+            }
+        }
+        let buildings = machines.into_iter()
+            .map(|machine| {
+                let mut proto = machine.proto;
+                proto.categories = machine.category_ids.iter().filter_map(|id| categories.get(id).cloned()).collect();
+                proto
+            })
+            .collect();
+
+        let productivity_recipes : HashSet<&str> = self.prototypes("module")
+            .flat_map(|(_, module)| string_list(module, "limitation"))
+            .collect();
+
+        let mut recipes = Vec::new();
+        for (id, prototype) in self.prototypes("recipe") {
+            let category_id = prototype.get("category").and_then(Value::as_str).unwrap_or(DEFAULT_RECIPE_CATEGORY);
+            let category = match categories.get(category_id) {
+                Some(&category) => category,
+                // No building can make it, such as recipes only made by hand.
+                None => continue,
+            };
+            // Recipes with difficulty variants keep them in `normal` and
+            // `expensive`; a variant of false means the recipe is disabled.
+            let variant = match prototype.get("normal") {
+                Some(Value::Object(normal)) => normal,
+                Some(_) => continue,
+                None => prototype,
+            };
+            let normal = self.recipe_cost(id, variant)?;
+            let expensive = match prototype.get("expensive") {
+                Some(Value::Object(expensive)) => self.recipe_cost(id, expensive)?,
+                // Recipes without difficulty variants cost the same in both.
+                _ => normal.clone(),
+            };
+            recipes.push(ProtoRecipe {
+                name: intern(&display_name(id)),
+                aliases: aliases(id),
+                inputs: normal.inputs,
+                outputs: normal.outputs,
+                time: normal.time,
+                category,
+                productivity: productivity_recipes.contains(id.as_str()),
+                expensive: Some(expensive),
+            });
+        }
+
+        // Mining a resource is a recipe taking the resource's mining time.
+        for (id, prototype) in self.prototypes("resource") {
+            let category_id = prototype.get("category").and_then(Value::as_str).unwrap_or(DEFAULT_RESOURCE_CATEGORY);
+            let category = match categories.get(category_id) {
+                Some(&category) => category,
+                None => continue,
+            };
+            let minable = match prototype.get("minable").and_then(Value::as_object) {
+                Some(minable) => minable,
+                None => continue,
+            };
+            let outputs = match (minable.get("results").and_then(Value::as_array), minable.get("result").and_then(Value::as_str)) {
+                (Some(results), _) => self.amounts(id, results)?,
+                (None, Some(result)) => {
+                    let count = minable.get("count").and_then(Value::as_f64).unwrap_or(1.0);
+                    vec![(self.resource(result), count as f32)]
+                },
+                (None, None) => continue,
+            };
+            let mut inputs = Vec::new();
+            if let Some(fluid) = minable.get("required_fluid").and_then(Value::as_str) {
+                let fluid_amount = minable.get("fluid_amount").and_then(Value::as_f64).unwrap_or(0.0) as f32;
+                inputs.push((self.resource(fluid), fluid_amount / FLUID_AMOUNT_PER));
+            }
+            let mut name = display_name(id);
+            if recipes.iter().any(|recipe| recipe.name == name) {
+                name = format!("Mining {}", name);
+            }
+            let cost = RecipeCost {
+                inputs,
+                outputs,
+                time: minable.get("mining_time").and_then(Value::as_f64).unwrap_or(1.0) as f32,
+            };
+            recipes.push(ProtoRecipe {
+                name: intern(&name),
+                aliases: aliases(id),
+                inputs: cost.inputs.clone(),
+                outputs: cost.outputs.clone(),
+                time: cost.time,
+                category,
+                productivity: true,
+                // Mining costs the same in both modes.
+                expensive: Some(cost),
+            });
+        }
+
+        let default_fuel = self.resource_ids.get("coal").cloned()
+            .filter(|coal| coal.fuel_value.is_some());
+        Ok(Database {
+            resources: self.resources,
+            categories: categories.into_values().collect(),
+            buildings,
+            recipes,
+            default_fuel,
+        })
+    }
+
+    fn prototypes(&self, prototype_type: &str) -> Box<dyn Iterator<Item=(&'a String, &'a Map<String, Value>)> + 'a> {
+        match self.raw.get(prototype_type).and_then(Value::as_object) {
+            Some(prototypes) => Box::new(prototypes.iter().filter_map(|(id, prototype)| prototype.as_object().map(|p| (id, p)))),
+            None => Box::new(None.into_iter()),
+        }
+    }
+
+    // The resource with the given id, adding it the first time it is seen.
+    fn resource(&mut self, id: &'a str) -> Resource {
+        if let Some(&resource) = self.resource_ids.get(id) {
+            return resource;
+        }
+        let mut resource = Resource::intern(&display_name(id));
+        // Anything with a stack size is an item; only items can be fuel.
+        resource.fuel_value = self.raw.values()
+            .filter_map(|prototypes| prototypes.get(id))
+            .filter(|prototype| prototype.get("stack_size").is_some())
+            .filter_map(|prototype| prototype.get("fuel_value").and_then(Value::as_str))
+            .filter_map(parse_energy)
+            .next();
+        resource.fluid = self.raw.get("fluid").and_then(|fluids| fluids.get(id)).is_some();
+        if let Some(&(hardness, infinite)) = self.mined.get(id) {
+            resource.mining_hardness = hardness;
+            resource.infinite = infinite;
+        }
+        self.resources.push(ProtoResource {
+            resource,
+            aliases: aliases(id),
+        });
+        self.resource_ids.insert(id, resource);
+        resource
+    }
+
+    fn recipe_cost(&mut self, id: &str, variant: &'a Map<String, Value>) -> Result<RecipeCost, InputError> {
+        let inputs = match variant.get("ingredients").and_then(Value::as_array) {
+            Some(ingredients) => self.amounts(id, ingredients)?,
+            None => return Err(InputError::new(&format!("Recipe {} has no ingredients", id))),
+        };
+        let outputs = match (variant.get("results").and_then(Value::as_array), variant.get("result").and_then(Value::as_str)) {
+            (Some(results), _) => self.amounts(id, results)?,
+            (None, Some(result)) => {
+                let count = variant.get("result_count").and_then(Value::as_f64).unwrap_or(1.0);
+                vec![(self.resource(result), count as f32)]
+            },
+            (None, None) => return Err(InputError::new(&format!("Recipe {} has no results", id))),
+        };
+        Ok(RecipeCost {
+            inputs,
+            outputs,
+            time: variant.get("energy_required").and_then(Value::as_f64).map_or(DEFAULT_RECIPE_TIME, |t| t as f32),
+        })
+    }
+
+    // Reads ingredients or results, which are either `[name, amount]` pairs
+    // or objects with a name and an amount, range of amounts or probability.
+    fn amounts(&mut self, recipe_id: &str, entries: &'a [Value]) -> Result<Vec<(Resource, f32)>, InputError> {
+        let mut amounts = Vec::new();
+        for entry in entries.iter() {
+            let (id, amount) = match *entry {
+                Value::Array(ref pair) if pair.len() == 2 => (pair[0].as_str(), pair[1].as_f64()),
+                Value::Object(ref object) => {
+                    let amount = object.get("amount").and_then(Value::as_f64).or_else(|| {
+                        let min = object.get("amount_min").and_then(Value::as_f64)?;
+                        let max = object.get("amount_max").and_then(Value::as_f64)?;
+                        Some((min + max) / 2.0)
+                    });
+                    let probability = object.get("probability").and_then(Value::as_f64).unwrap_or(1.0);
+                    (object.get("name").and_then(Value::as_str), amount.map(|a| a * probability))
+                },
+                _ => (None, None),
+            };
+            match (id, amount) {
+                (Some(id), Some(amount)) => amounts.push((self.resource(id), amount as f32)),
+                _ => return Err(InputError::new(&format!("Malformed ingredient or result in {}", recipe_id))),
+            }
+        }
+        Ok(amounts)
+    }
+}
+
+fn building(id: &str, prototype: &Map<String, Value>, speed_key: &str, default_drain: bool) -> Result<ProtoBuilding, InputError> {
+    let energy_consumption = prototype.get("energy_usage").and_then(Value::as_str).and_then(parse_energy)
+        .ok_or_else(|| InputError::new(&format!("Building {} has no energy usage", id)))?;
+    let energy_source = prototype.get("energy_source").and_then(Value::as_object);
+    let source_type = energy_source.and_then(|source| source.get("type")).and_then(Value::as_str);
+    // Only given per minute by newer versions of the game.
+    let pollution = energy_source.and_then(|source| source.get("emissions_per_minute")).and_then(Value::as_f64).unwrap_or(0.0) as f32;
+    let (energy_source, drain) = match source_type {
+        Some("burner") => {
+            let efficiency = energy_source.and_then(|source| source.get("effectivity")).and_then(Value::as_f64).unwrap_or(1.0);
+            (EnergySource::Burner { efficiency: efficiency as f32 }, 0.0)
+        },
+        _ => {
+            let drain = energy_source.and_then(|source| source.get("drain")).and_then(Value::as_str).and_then(parse_energy)
+                .unwrap_or(if default_drain { energy_consumption / DEFAULT_DRAIN_DIVISOR } else { 0.0 });
+            (EnergySource::Electric, drain)
+        },
+    };
+    let module_slots = prototype.get("module_specification")
+        .and_then(|modules| modules.get("module_slots"))
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
+    Ok(ProtoBuilding {
+        name: intern(&display_name(id)),
+        energy_consumption,
+        drain,
+        crafting_speed: prototype.get(speed_key).and_then(Value::as_f64).unwrap_or(1.0) as f32,
+        module_slots: module_slots as i16,
+        mining_power: 0.0,
+        energy_source,
+        categories: Vec::new(),
+        neighbor_bonus: 0.0,
+        accumulators: 0.0,
+        pollution,
+    })
+}
+
+// Orders the buildings that can run a category, best for planning last.
+fn planning_rank(proto: &ProtoBuilding) -> (bool, f32) {
+    (proto.energy_source == EnergySource::Electric, proto.crafting_speed)
+}
+
+fn string_list<'a>(prototype: &'a Map<String, Value>, key: &str) -> Vec<&'a str> {
+    prototype.get(key)
+        .and_then(Value::as_array)
+        .map_or_else(Vec::new, |values| values.iter().filter_map(Value::as_str).collect())
+}
+
+// Turns an id such as "iron-gear-wheel" into "Iron Gear Wheel".
+fn display_name(id: &str) -> String {
+    id.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn aliases(id: &str) -> Vec<&'static str> {
+    if display_name(id).to_lowercase() == id.to_lowercase() {
+        Vec::new()
+    } else {
+        vec![intern(id)]
+    }
+}
+
+// Reads an amount of energy or power such as "150kW" or "8MJ", in kJ or kW.
+fn parse_energy(value: &str) -> Option<f32> {
+    let value = value.trim_end_matches(['J', 'W']);
+    let (number, scale) = match value.chars().last() {
+        Some('k') => (&value[..value.len() - 1], 1.0),
+        Some('M') => (&value[..value.len() - 1], 1e3),
+        Some('G') => (&value[..value.len() - 1], 1e6),
+        _ => (value, 1e-3),
+    };
+    number.parse::<f32>().ok().map(|n| n * scale)
+}
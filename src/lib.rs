@@ -0,0 +1,33 @@
+#[macro_use]
+extern crate lazy_static;
+extern crate nom;
+#[macro_use]
+extern crate serde_json;
+
+mod analysis;
+mod building;
+mod database;
+mod design;
+mod error;
+mod import;
+mod logistics;
+mod modules;
+mod optimize;
+mod parse;
+mod recipe;
+mod research;
+mod resource;
+mod simplex;
+mod system;
+
+pub use analysis::{Analysis, BuildingAnalysis};
+pub use building::{Beacons, Building, EnergySource, ProtoBuilding};
+pub use database::Database;
+pub use design::{Design, ResourceLine};
+pub use error::{AnalyzeError, InputError, Location};
+pub use logistics::{pipe_throughput, Belt, Inserter, BELTS, INSERTERS};
+pub use modules::{Modifiers, Module};
+pub use optimize::Objective;
+pub use recipe::{ProtoRecipe, Recipe, RecipeCategory, RecipeCost, RecipeMode};
+pub use research::Research;
+pub use resource::{ProtoResource, Resource};
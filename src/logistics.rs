@@ -0,0 +1,75 @@
+use research::Research;
+
+// A tier of transport belt, and how many items per second a full belt
+// carries. Each of its two lanes carries half.
+#[derive(Debug, Clone, Copy)]
+pub struct Belt {
+    pub name: &'static str,
+    pub items_per_second: f32,
+}
+
+pub const BELTS : [Belt; 3] = [
+    Belt { name: "Transport Belt", items_per_second: 40.0 / 3.0 },
+    Belt { name: "Fast Transport Belt", items_per_second: 80.0 / 3.0 },
+    Belt { name: "Express Transport Belt", items_per_second: 40.0 },
+];
+
+// A kind of inserter, and how many swings per second it makes moving items
+// between two machines or chests.
+#[derive(Debug, Clone, Copy)]
+pub struct Inserter {
+    pub name: &'static str,
+    pub swings_per_second: f32,
+    // Stack inserters get a much larger capacity bonus.
+    pub stack: bool,
+}
+
+pub const INSERTERS : [Inserter; 5] = [
+    Inserter { name: "Burner Inserter", swings_per_second: 0.6, stack: false },
+    Inserter { name: "Inserter", swings_per_second: 0.83, stack: false },
+    Inserter { name: "Long Handed Inserter", swings_per_second: 1.15, stack: false },
+    Inserter { name: "Fast Inserter", swings_per_second: 2.31, stack: false },
+    Inserter { name: "Stack Inserter", swings_per_second: 2.31, stack: true },
+];
+
+// Inserters that fit along one side of a 3x3 machine.
+pub const INSERTERS_PER_SIDE : u32 = 3;
+
+impl Inserter {
+    pub fn items_per_second(&self, research: &Research) -> f32 {
+        self.swings_per_second * research.inserter_stack_size(self.stack) as f32
+    }
+}
+
+// A pump pushes this much fluid per second, and keeps that rate up through
+// this many pipes before another pump is needed.
+pub const PUMP_THROUGHPUT : f32 = 1200.0;
+pub const PUMP_SPACING : u32 = 17;
+
+// Pipes without pumps are assumed to be this long when a design does not
+// give a length.
+pub const DEFAULT_PIPE_LENGTH : u32 = PUMP_SPACING;
+
+// The most fluid per second that moves through an unpumped pipe of a given
+// number of segments, measured in game. Lengths in between are interpolated.
+const PIPE_THROUGHPUTS : [(u32, f32); 19] = [
+    (1, 6000.0), (2, 3000.0), (3, 3000.0), (7, 2000.0), (12, 1500.0),
+    (17, 1200.0), (20, 1125.0), (30, 1000.0), (50, 900.0), (100, 800.0),
+    (150, 750.0), (200, 720.0), (261, 600.0), (300, 400.0), (400, 300.0),
+    (500, 240.0), (600, 200.0), (800, 150.0), (1000, 120.0),
+];
+
+pub fn pipe_throughput(length: u32) -> f32 {
+    let length = length.max(1);
+    for window in PIPE_THROUGHPUTS.windows(2) {
+        let (short, short_rate) = window[0];
+        let (long, long_rate) = window[1];
+        if length <= long {
+            let fraction = (length - short) as f32 / (long - short) as f32;
+            return short_rate + (long_rate - short_rate) * fraction;
+        }
+    }
+    // Past the last measurement, throughput falls off with length.
+    let (longest, longest_rate) = PIPE_THROUGHPUTS[PIPE_THROUGHPUTS.len() - 1];
+    longest_rate * longest as f32 / length as f32
+}
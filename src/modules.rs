@@ -0,0 +1,131 @@
+use error::InputError;
+
+lazy_static! {
+    static ref MODULE_NAMES : Vec<(Module, &'static str)> = vec![
+        (Module::Productivity1, "Productivity 1"),
+        (Module::Productivity1, "Productivity1"),
+        (Module::Productivity1, "Productivity"),
+        (Module::Productivity2, "Productivity 2"),
+        (Module::Productivity2, "Productivity2"),
+        (Module::Productivity3, "Productivity 3"),
+        (Module::Productivity3, "Productivity3"),
+        (Module::Speed1, "Speed 1"),
+        (Module::Speed1, "Speed1"),
+        (Module::Speed1, "Speed"),
+        (Module::Speed2, "Speed 2"),
+        (Module::Speed2, "Speed2"),
+        (Module::Speed3, "Speed 3"),
+        (Module::Speed3, "Speed3"),
+        (Module::Efficiency1, "Efficiency 1"),
+        (Module::Efficiency1, "Efficiency1"),
+        (Module::Efficiency1, "Efficiency"),
+        (Module::Efficiency2, "Efficiency 2"),
+        (Module::Efficiency2, "Efficiency2"),
+        (Module::Efficiency3, "Efficiency 3"),
+        (Module::Efficiency3, "Efficiency3"),
+    ];
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Module {
+    Productivity1,
+    Productivity2,
+    Productivity3,
+    Speed1,
+    Speed2,
+    Speed3,
+    Efficiency1,
+    Efficiency2,
+    Efficiency3,
+}
+
+impl Module {
+    pub fn display_name(&self) -> &'static str {
+        match *self {
+            Module::Productivity1 => "Productivity 1",
+            Module::Productivity2 => "Productivity 2",
+            Module::Productivity3 => "Productivity 3",
+            Module::Speed1 => "Speed 1",
+            Module::Speed2 => "Speed 2",
+            Module::Speed3 => "Speed 3",
+            Module::Efficiency1 => "Efficiency 1",
+            Module::Efficiency2 => "Efficiency 2",
+            Module::Efficiency3 => "Efficiency 3",
+        }
+    }
+
+    pub fn is_productivity(&self) -> bool {
+        matches!(*self, Module::Productivity1 | Module::Productivity2 | Module::Productivity3)
+    }
+
+    pub fn from_name(name: &str) -> Result<Module, InputError> {
+        for &(module_type, module_name) in MODULE_NAMES.iter() {
+            if name.to_lowercase() == module_name.to_lowercase() {
+                return Ok(module_type)
+            }
+        }
+        Err(InputError::new(&format!("Unknown module: {}", name)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Modifiers {
+    pub speed: f32,
+    pub productivity: f32,
+    pub energy: f32,
+}
+
+impl Modifiers {
+    pub(crate) fn new() -> Modifiers {
+        Modifiers {
+            speed: 0.0,
+            productivity: 0.0,
+            energy: 1.0,
+        }
+    }
+
+    pub fn from_modules<I>(modules: &[(Module, I)]) -> Modifiers where I: Copy + Into<f32> {
+        let mut modifiers = Modifiers::new();
+        for &(module, count) in modules {
+            match module {
+                Module::Productivity1 => {
+                    modifiers.speed += (-0.15) * count.into();
+                    modifiers.productivity += 0.04 * count.into();
+                    modifiers.energy += 0.40 * count.into();
+                },
+                Module::Productivity2 => {
+                    modifiers.speed += (-0.15) * count.into();
+                    modifiers.productivity += 0.06 * count.into();
+                    modifiers.energy += 0.60 * count.into();
+                },
+                Module::Productivity3 => {
+                    modifiers.speed += (-0.15) * count.into();
+                    modifiers.productivity += 0.10 * count.into();
+                    modifiers.energy += 0.80 * count.into();
+                },
+                Module::Speed1 => {
+                    modifiers.speed += 0.20 * count.into();
+                    modifiers.energy += 0.50 * count.into();
+                },
+                Module::Speed2 => {
+                    modifiers.speed += 0.30 * count.into();
+                    modifiers.energy += 0.60 * count.into();
+                },
+                Module::Speed3 => {
+                    modifiers.speed += 0.50 * count.into();
+                    modifiers.energy += 0.70 * count.into();
+                },
+                Module::Efficiency1 => {
+                    modifiers.energy += (-0.30) * count.into();
+                },
+                Module::Efficiency2 => {
+                    modifiers.energy += (-0.40) * count.into();
+                },
+                Module::Efficiency3 => {
+                    modifiers.energy += (-0.50) * count.into();
+                },
+            }
+        }
+        modifiers
+    }
+}
@@ -0,0 +1,204 @@
+use std::str::FromStr;
+
+use analysis::Analysis;
+use building::Building;
+use design::Design;
+use error::{AnalyzeError, InputError};
+use modules::Module;
+use simplex::{LinearProgram, ProgramError, Relation};
+
+// What the optimizer minimizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    // Items per second taken from the design's input lines.
+    RawInputs,
+    // Electric energy used by buildings and beacons, in kW.
+    Power,
+    // Machines placed, not counting beacons.
+    Buildings,
+    // Pollution per minute.
+    Pollution,
+}
+
+// Counts below this are treated as buildings the optimizer left out.
+const UNUSED_COUNT : f64 = 1e-6;
+
+impl FromStr for Objective {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> Result<Objective, InputError> {
+        match &s.to_lowercase()[..] {
+            "raw inputs" => Ok(Objective::RawInputs),
+            "power" => Ok(Objective::Power),
+            "buildings" => Ok(Objective::Buildings),
+            "pollution" => Ok(Objective::Pollution),
+            _ => Err(InputError::new(&format!("Unknown objective: {}; expected Raw Inputs, Power, Buildings or Pollution", s))),
+        }
+    }
+}
+
+impl Objective {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Objective::RawInputs => "raw inputs",
+            Objective::Power => "power",
+            Objective::Buildings => "buildings",
+            Objective::Pollution => "pollution",
+        }
+    }
+
+    // The value of the objective for an analyzed design.
+    pub fn value(&self, design: &Design, analysis: &Analysis) -> f32 {
+        match *self {
+            Objective::RawInputs => design.input_lines.iter()
+                .filter(|line_name| !design.resource_lines[*line_name].resource_type.is_energy())
+                .map(|line_name| -analysis.line_rates[line_name])
+                .sum(),
+            Objective::Power => analysis.total_energy,
+            Objective::Buildings => analysis.buildings.iter().map(|building| building.count).sum(),
+            Objective::Pollution => analysis.buildings.iter().map(|building| building.pollution).sum(),
+        }
+    }
+
+    // How much one copy of a building adds to the objective. Raw inputs are
+    // counted through the lines instead.
+    fn building_cost(&self, building: &Building) -> f64 {
+        match *self {
+            Objective::RawInputs => 0.0,
+            Objective::Power => f64::from(building.electric_energy_consumption() + building.beacon_energy_consumption()),
+            Objective::Buildings => 1.0,
+            Objective::Pollution => f64::from(building.pollution_rate()),
+        }
+    }
+}
+
+impl Design {
+    // Chooses how many of each building to run, and which modules to put in
+    // buildings with Allowed Modules, to meet the targets at the lowest
+    // value of the design's objective. Unlike analysis, every building may
+    // go unused and lines need not balance exactly: inputs may go unused and
+    // outputs without a target may be left at zero. That lets a design list
+    // alternative recipes for a line side by side. The buildings that are
+    // used are returned as a design of their own, along with its analysis.
+    pub fn optimize(&self) -> Result<(Design, Analysis), AnalyzeError> {
+        let objective = self.objective.ok_or_else(|| AnalyzeError::new("The design has no Optimize objective"))?;
+        if self.targets.is_empty() && self.buildings.iter().all(|building| building.count.is_none()) {
+            return Err(AnalyzeError::new("Optimizing needs a target or a building with a fixed Count"));
+        }
+
+        // Each building is a candidate as given, or once for every way of
+        // filling its slots with allowed modules. Candidates remember which
+        // building they came from, so that counts apply to all of them.
+        let mut candidates : Vec<(usize, Building)> = Vec::new();
+        for (position, building) in self.buildings.iter().enumerate() {
+            if building.allowed_modules.is_empty() {
+                candidates.push((position, building.clone()));
+                continue;
+            }
+            for modules in module_choices(&building.allowed_modules, building.module_slots) {
+                let mut candidate = building.clone();
+                candidate.modules = modules;
+                candidates.push((position, candidate));
+            }
+        }
+
+        // Each candidate's net use of every line.
+        let num_lines = self.next_index;
+        let line_use = self.line_use(candidates.iter().map(|(_, building)| building));
+
+        let mut program = LinearProgram::new(candidates.len());
+        for line in self.resource_lines.values() {
+            let coefficients = line_use[line.index].clone();
+            let target = self.targets.iter().find(|(name, _)| *name == line.name);
+            match target {
+                // Lines are netted as production minus use.
+                Some(&(_, target)) => program.add_constraint(coefficients, Relation::Equal, -f64::from(target)),
+                None => {
+                    let is_input = self.input_lines.contains(&line.name);
+                    // Overflow lines, like outputs, may be left with a surplus.
+                    let is_output = self.output_lines.contains(&line.name) || self.overflow_lines.contains(&line.name);
+                    match (is_input, is_output) {
+                        (true, true) => {},
+                        (true, false) => program.add_constraint(coefficients, Relation::AtLeast, 0.0),
+                        (false, true) => program.add_constraint(coefficients, Relation::AtMost, 0.0),
+                        (false, false) => program.add_constraint(coefficients, Relation::Equal, 0.0),
+                    }
+                },
+            }
+        }
+        for (position, building) in self.buildings.iter().enumerate() {
+            let coefficients : Vec<f64> = candidates.iter()
+                .map(|&(from, _)| if from == position { 1.0 } else { 0.0 })
+                .collect();
+            if let Some(count) = building.count {
+                program.add_constraint(coefficients.clone(), Relation::Equal, f64::from(count));
+            }
+            if let Some(minimum) = building.minimum_count {
+                program.add_constraint(coefficients.clone(), Relation::AtLeast, f64::from(minimum));
+            }
+            if let Some(maximum) = building.maximum_count {
+                program.add_constraint(coefficients, Relation::AtMost, f64::from(maximum));
+            }
+        }
+
+        let mut costs : Vec<f64> = candidates.iter().map(|(_, building)| objective.building_cost(building)).collect();
+        if objective == Objective::RawInputs {
+            for line in self.resource_lines.values() {
+                if self.input_lines.contains(&line.name) && !line.resource_type.is_energy() {
+                    for (cost, &used) in costs.iter_mut().zip(line_use[line.index].iter()) {
+                        *cost += used;
+                    }
+                }
+            }
+        }
+
+        let counts = match program.minimize(&costs) {
+            Ok(counts) => counts,
+            Err(ProgramError::Infeasible) => return Err(AnalyzeError::new(
+                "No choice of buildings meets every target and count; add an input, or a building that makes what is missing")),
+            Err(ProgramError::Unbounded) => return Err(AnalyzeError::new(
+                "The objective can be made as small as we like, such as by running a loop that produces inputs; add an output or a target")),
+        };
+
+        // The chosen buildings become the buildings of a new design, with
+        // indices after the lines so that several module choices for one
+        // building can sit side by side.
+        let mut chosen = self.clone();
+        chosen.buildings.clear();
+        let mut solution = vec![0.0; num_lines];
+        for (line_index, uses) in line_use.iter().enumerate() {
+            solution[line_index] = -uses.iter().zip(counts.iter()).map(|(used, count)| used * count).sum::<f64>() as f32;
+        }
+        for ((_, building), &count) in candidates.iter().zip(counts.iter()) {
+            if count > UNUSED_COUNT {
+                let mut building = building.clone();
+                building.index = chosen.next_index;
+                chosen.next_index += 1;
+                solution.push(count as f32);
+                chosen.buildings.push(building);
+            }
+        }
+        let analysis = Analysis::from_solution(&chosen, &solution);
+        Ok((chosen, analysis))
+    }
+}
+
+// Every way of putting up to `slots` modules of the allowed kinds into a
+// building, including leaving slots empty.
+fn module_choices(allowed: &[Module], slots: i16) -> Vec<Vec<(Module, i16)>> {
+    match allowed.split_first() {
+        None => vec![Vec::new()],
+        Some((&module, rest)) => {
+            let mut choices = Vec::new();
+            for count in 0..slots + 1 {
+                for mut choice in module_choices(rest, slots - count) {
+                    if count > 0 {
+                        choice.insert(0, (module, count));
+                    }
+                    choices.push(choice);
+                }
+            }
+            choices
+        },
+    }
+}
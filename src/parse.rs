@@ -0,0 +1,187 @@
+use std;
+
+use nom::*;
+
+use error::InputError;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Data<'a> {
+    pub(crate) value: &'a str,
+    pub(crate) children: Vec<Data<'a>>,
+    // Where `value` starts in the original file, counting from 1. These are
+    // filled in once the whole file has been parsed.
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+impl<'a> Data<'a> {
+    fn leaf(value: &'a str) -> Data<'a> {
+        Data {
+            value,
+            children: Vec::new(),
+            line: 0,
+            column: 0,
+        }
+    }
+
+    pub(crate) fn from_cleaned(cleaned: &'a Cleaned) -> Result<Vec<Data<'a>>, InputError> {
+        let input = &cleaned.contents[..];
+        let iresult : IResult<&[u8], Vec<Data>> = complete!(input, many0!(call!(node, b"")));
+        let (rest, mut data) = match iresult {
+            IResult::Done(rest, data) => (rest, data),
+            _ => return Err(InputError::new("Malformed design file")),
+        };
+        if !rest.is_empty() {
+            // The parser stops at the first line it cannot make sense of.
+            let indentation = rest.iter().take_while(|&&b| b == b' ' || b == b'\t').count();
+            let (line, column) = cleaned.position(input.len() - rest.len() + indentation);
+            let text = String::from_utf8_lossy(rest[indentation..].split(|&b| b == b'\n').next().unwrap_or(&[]));
+            return Err(InputError::new("Unable to parse line").at_position(line, column, &text));
+        }
+        for datum in data.iter_mut() {
+            datum.locate(cleaned);
+        }
+        Ok(data)
+    }
+
+    fn locate(&mut self, cleaned: &Cleaned) {
+        let (line, column) = cleaned.position(self.value.as_ptr() as usize - cleaned.contents.as_ptr() as usize);
+        self.line = line;
+        self.column = column;
+        for child in self.children.iter_mut() {
+            child.locate(cleaned);
+        }
+    }
+}
+
+// The contents of a file after cleaning, along with the original line number
+// of each remaining line.
+pub(crate) struct Cleaned {
+    pub(crate) contents: Vec<u8>,
+    line_numbers: Vec<usize>,
+}
+
+impl Cleaned {
+    // Maps a byte offset into the cleaned contents to a line and column of
+    // the original file. Cleaning never moves text within a line, so only
+    // the line number needs translating.
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let before = &self.contents[..offset];
+        let cleaned_line = before.iter().filter(|&&b| b == b'\n').count();
+        let line_start = before.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+        let line = self.line_numbers.get(cleaned_line).cloned().unwrap_or(0);
+        (line + 1, offset - line_start + 1)
+    }
+}
+
+// Remove comments (any characters after a # in a line),
+// trailing whitespace, and any pure-whitespace lines.
+pub(crate) fn clean(data: &[u8]) -> Cleaned {
+    let mut cleaned_contents = Vec::new();
+    let mut line_numbers = Vec::new();
+    let mut line_number = 0;
+    let mut spaces = Vec::new();
+    let mut in_comment = false;
+    let mut line_filled = false;
+
+    for &b in data {
+        if b == b'\n' {
+            if line_filled {
+                cleaned_contents.push(b'\n');
+                line_numbers.push(line_number);
+            }
+            line_number += 1;
+            spaces.clear();
+            in_comment = false;
+            line_filled = false;
+        } else if b == b' ' || b == b'\t' {
+            if !in_comment {
+                spaces.push(b);
+            }
+        } else if b == b'#' {
+            in_comment = true;
+        } else {
+            if !in_comment {
+                cleaned_contents.extend(spaces.iter());
+                cleaned_contents.push(b);
+                line_filled = true;
+                spaces.clear();
+            }
+        }
+    }
+
+    // The parser expects every line to be terminated, including the last.
+    if line_filled {
+        cleaned_contents.push(b'\n');
+        line_numbers.push(line_number);
+    }
+
+    Cleaned {
+        contents: cleaned_contents,
+        line_numbers,
+    }
+}
+
+fn match_indentation<'a>(input: &'a [u8], indentation: &[u8]) -> IResult<&'a [u8], ()> {
+    do_parse!(input,
+        verify!(opt!(is_a!(" \t")), |line_ind: Option<&[u8]>| line_ind.unwrap_or(&[]) == indentation) >>
+        (())
+    )
+}
+
+fn deeper_indentation<'a>(input: &'a [u8], indentation: &[u8]) -> IResult<&'a [u8], &'a [u8]> {
+    do_parse!(input,
+        new_indentation: verify!(
+            map!(opt!(is_a!(" \t")), |x: Option<&'a [u8]>| x.unwrap_or(b"")),
+            |line_ind: &[u8]| line_ind.starts_with(indentation) && line_ind != indentation
+        ) >>
+        (new_indentation)
+    )
+}
+
+fn inline_node<'a>(input: &'a [u8], indentation: &[u8]) -> IResult<&'a [u8], Data<'a>> {
+    do_parse!(input, 
+        call!(match_indentation, indentation) >>
+        value: map_res!(is_not!(":\n"), std::str::from_utf8) >>
+        tag!(":") >>
+        is_a!(" \t") >>
+        children: separated_list!(
+                do_parse!(
+                    tag!(",") >>
+                    opt!(is_a!(" \t")) >>
+                    (())
+                ),
+                map!(map_res!(is_not!(",\n"), std::str::from_utf8), Data::leaf)
+            ) >>
+        tag!("\n") >>
+        (Data {
+            value,
+            children,
+            line: 0,
+            column: 0,
+        })
+    )
+}
+
+fn nested_node<'a>(input: &'a [u8], indentation: &[u8]) -> IResult<&'a [u8], Data<'a>> {
+    do_parse!(input,
+        call!(match_indentation, indentation) >>
+        value: map_res!(is_not!(":\n"), std::str::from_utf8) >>
+        tag!("\n") >>
+        children: opt!(do_parse!(
+            new_indentation: peek!(call!(deeper_indentation, indentation)) >>
+            children: many1!(call!(node, new_indentation)) >>
+            (children)
+        )) >>
+        (Data {
+            value,
+            children: children.unwrap_or_else(Vec::new),
+            line: 0,
+            column: 0,
+        })
+    )
+}
+
+fn node<'a>(input: &'a [u8], indentation: &[u8]) -> IResult<&'a [u8], Data<'a>> {
+    alt!(input, call!(inline_node, indentation) | call!(nested_node, indentation))
+}
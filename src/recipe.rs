@@ -0,0 +1,106 @@
+use std::str::FromStr;
+
+use database::Database;
+use design::ResourceLine;
+use error::InputError;
+use resource::Resource;
+
+#[derive(Debug, Clone)]
+pub struct Recipe {
+    pub name: &'static str,
+    pub inputs: Vec<(ResourceLine, f32)>,
+    pub outputs: Vec<(ResourceLine, f32)>,
+    pub time: f32,
+    pub category: RecipeCategory,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProtoRecipe {
+    pub name: &'static str,
+    pub aliases: Vec<&'static str>,
+    pub inputs: Vec<(Resource, f32)>,
+    pub outputs: Vec<(Resource, f32)>,
+    pub time: f32,
+    pub category: RecipeCategory,
+    // Whether productivity modules can be used on the recipe, which is only
+    // the case for intermediate products.
+    pub productivity: bool,
+    // The costs in expensive mode, for recipes where they differ.
+    pub expensive: Option<RecipeCost>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecipeCost {
+    pub inputs: Vec<(Resource, f32)>,
+    pub outputs: Vec<(Resource, f32)>,
+    pub time: f32,
+}
+
+// Which set of recipe costs a design is read with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecipeMode {
+    #[default]
+    Normal,
+    Expensive,
+}
+
+impl FromStr for RecipeMode {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> Result<RecipeMode, InputError> {
+        match &s.to_lowercase()[..] {
+            "normal" => Ok(RecipeMode::Normal),
+            "expensive" => Ok(RecipeMode::Expensive),
+            _ => Err(InputError::new(&format!("Unknown recipe mode: {}", s))),
+        }
+    }
+}
+
+// The kind of building a recipe is made in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecipeCategory {
+    pub name: &'static str,
+    // The building used for recipes of this category when the buildings of
+    // a design are planned automatically.
+    pub default_building: &'static str,
+    // Mining recipes get the bonus from mining productivity research.
+    pub mining: bool,
+}
+
+impl ProtoRecipe {
+    // Picks the recipe used to make `resource` when none is given in the
+    // design, which is the first of its candidates.
+    pub fn default_for(resource: Resource, mode: RecipeMode) -> Option<ProtoRecipe> {
+        ProtoRecipe::candidates_for(resource, mode).into_iter().next()
+    }
+
+    // The recipes that make `resource`, best first. Recipes with `resource`
+    // as their only output are preferred, and recipes that consume their own
+    // product (such as Kovarex enrichment) are left out.
+    pub fn candidates_for(resource: Resource, mode: RecipeMode) -> Vec<ProtoRecipe> {
+        let database = Database::current();
+        let mut candidates : Vec<ProtoRecipe> = database.recipes().iter()
+            .map(|proto| proto.in_mode(mode))
+            .filter(|proto| proto.outputs.iter().any(|&(output, _)| output == resource))
+            .filter(|proto| proto.inputs.iter().all(|&(input, _)| input != resource))
+            .collect();
+        // The sort is stable, so recipes otherwise keep the order of the data.
+        candidates.sort_by_key(|proto| proto.outputs.len() != 1);
+        candidates
+    }
+
+    pub fn from_name(name: &str, mode: RecipeMode) -> Option<ProtoRecipe> {
+        Database::current().find_recipe(name).map(|proto| proto.in_mode(mode))
+    }
+
+    // The recipe with the costs used in `mode`.
+    pub fn in_mode(&self, mode: RecipeMode) -> ProtoRecipe {
+        let mut proto = self.clone();
+        if let (RecipeMode::Expensive, Some(cost)) = (mode, proto.expensive.take()) {
+            proto.inputs = cost.inputs;
+            proto.outputs = cost.outputs;
+            proto.time = cost.time;
+        }
+        proto
+    }
+}
@@ -0,0 +1,41 @@
+use recipe::RecipeCategory;
+
+// Researched bonuses that change how fast buildings produce.
+#[derive(Debug, Clone, Default)]
+pub struct Research {
+    // Each level of mining productivity gives mining drills and pumpjacks
+    // 10% extra output.
+    pub mining_productivity: u32,
+    // Inserter capacity bonus, which lets inserters carry several items at
+    // once.
+    pub inserter_capacity: u32,
+}
+
+const MINING_PRODUCTIVITY_PER_LEVEL : f32 = 0.1;
+
+// Items carried per swing at each level of inserter capacity research, for
+// ordinary inserters and for stack inserters.
+const INSERTER_STACK_SIZES : [u32; 8] = [1, 1, 2, 2, 2, 2, 2, 3];
+const STACK_INSERTER_STACK_SIZES : [u32; 8] = [1, 2, 3, 5, 7, 9, 11, 12];
+pub const MAX_INSERTER_CAPACITY : u32 = 7;
+
+impl Research {
+    // The productivity buildings running recipes of this category get
+    // before modules.
+    pub fn base_productivity(&self, category: RecipeCategory) -> f32 {
+        if category.mining {
+            MINING_PRODUCTIVITY_PER_LEVEL * self.mining_productivity as f32
+        } else {
+            0.0
+        }
+    }
+
+    pub fn inserter_stack_size(&self, stack_inserter: bool) -> u32 {
+        let level = self.inserter_capacity.min(MAX_INSERTER_CAPACITY) as usize;
+        if stack_inserter {
+            STACK_INSERTER_STACK_SIZES[level]
+        } else {
+            INSERTER_STACK_SIZES[level]
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use database::{intern, Database};
+use error::InputError;
+
+// A resource, identified by its name in the game data. Resources carry what
+// the data they were read from says about them, so they can be used without
+// knowing which `Database` is current. Two resources with the same name are
+// the same resource.
+#[derive(Debug, Copy, Clone)]
+pub struct Resource {
+    name: &'static str,
+    // How much harder the resource is to mine than a resource of hardness 0.
+    pub(crate) mining_hardness: f32,
+    // The energy released by burning one item, in kJ, or None if the
+    // resource is not a fuel.
+    pub(crate) fuel_value: Option<f32>,
+    // Energy resources are measured in kW rather than items per second.
+    pub(crate) energy: bool,
+    // Electric buildings draw from lines of the power grid resource.
+    pub(crate) power_grid: bool,
+    // Infinite resources are mined at a rate set by the yield of the field.
+    pub(crate) infinite: bool,
+    // Fluids move through pipes rather than on belts.
+    pub(crate) fluid: bool,
+}
+
+// What the game data says about a resource.
+#[derive(Debug, Clone)]
+pub struct ProtoResource {
+    pub resource: Resource,
+    pub aliases: Vec<&'static str>,
+}
+
+impl PartialEq for Resource {
+    fn eq(&self, other: &Resource) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Resource {}
+
+impl Hash for Resource {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl PartialOrd for Resource {
+    fn partial_cmp(&self, other: &Resource) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Resource {
+    fn cmp(&self, other: &Resource) -> Ordering {
+        self.name.cmp(other.name)
+    }
+}
+
+impl FromStr for Resource {
+    type Err = InputError;
+
+    fn from_str(name: &str) -> Result<Resource, InputError> {
+        Database::current().find_resource(name)
+            .ok_or_else(|| InputError::new(&format!("Unknown resource: {}", name)))
+    }
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl Resource {
+    // A resource with none of the properties set.
+    pub(crate) fn intern(name: &str) -> Resource {
+        Resource {
+            name: intern(name),
+            mining_hardness: 0.0,
+            fuel_value: None,
+            energy: false,
+            power_grid: false,
+            infinite: false,
+            fluid: false,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn mining_hardness(&self) -> f32 {
+        self.mining_hardness
+    }
+
+    pub fn is_energy(&self) -> bool {
+        self.energy
+    }
+
+    pub fn is_power_grid(&self) -> bool {
+        self.power_grid
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        self.infinite
+    }
+
+    pub fn is_fluid(&self) -> bool {
+        self.fluid
+    }
+
+    pub fn fuel_value(&self) -> Option<f32> {
+        self.fuel_value
+    }
+}
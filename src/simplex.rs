@@ -0,0 +1,285 @@
+// A linear program over non-negative variables, solved by the two-phase
+// simplex method on a dense tableau. Bland's rule picks the pivots, which is
+// slow on large programs but never cycles, and designs are small.
+pub(crate) struct LinearProgram {
+    num_variables: usize,
+    constraints: Vec<(Vec<f64>, Relation, f64)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Relation {
+    Equal,
+    AtLeast,
+    AtMost,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ProgramError {
+    // No values of the variables meet every constraint.
+    Infeasible,
+    // The objective can be made as small as we like.
+    Unbounded,
+}
+
+const EPSILON : f64 = 1e-9;
+
+struct Tableau {
+    // Each row holds the coefficients of every column followed by the
+    // value of the row's basic variable.
+    rows: Vec<Vec<f64>>,
+    basis: Vec<usize>,
+    num_columns: usize,
+}
+
+impl LinearProgram {
+    pub(crate) fn new(num_variables: usize) -> LinearProgram {
+        LinearProgram {
+            num_variables,
+            constraints: Vec::new(),
+        }
+    }
+
+    pub(crate) fn add_constraint(&mut self, coefficients: Vec<f64>, relation: Relation, rhs: f64) {
+        assert_eq!(coefficients.len(), self.num_variables);
+        self.constraints.push((coefficients, relation, rhs));
+    }
+
+    pub(crate) fn minimize(&self, objective: &[f64]) -> Result<Vec<f64>, ProgramError> {
+        assert_eq!(objective.len(), self.num_variables);
+        // Columns are the variables, then a slack or surplus variable for
+        // every inequality, then an artificial variable for every row that
+        // has no slack to start the basis with.
+        let num_slacks = self.constraints.iter().filter(|&&(_, relation, _)| relation != Relation::Equal).count();
+        let num_artificials = self.constraints.iter()
+            .filter(|&&(_, relation, rhs)| match relation {
+                Relation::Equal => true,
+                Relation::AtLeast => rhs >= 0.0,
+                Relation::AtMost => rhs < 0.0,
+            })
+            .count();
+        let first_artificial = self.num_variables + num_slacks;
+        let num_columns = first_artificial + num_artificials;
+
+        let mut tableau = Tableau {
+            rows: Vec::new(),
+            basis: Vec::new(),
+            num_columns,
+        };
+        let mut next_slack = self.num_variables;
+        let mut next_artificial = first_artificial;
+        for &(ref coefficients, relation, rhs) in self.constraints.iter() {
+            let mut row = vec![0.0; num_columns + 1];
+            row[..self.num_variables].copy_from_slice(coefficients);
+            match relation {
+                Relation::AtMost => row[next_slack] = 1.0,
+                Relation::AtLeast => row[next_slack] = -1.0,
+                Relation::Equal => {},
+            }
+            if relation != Relation::Equal {
+                next_slack += 1;
+            }
+            row[num_columns] = rhs;
+            // Keep every right hand side non-negative.
+            if rhs < 0.0 {
+                for value in row.iter_mut() {
+                    *value = -*value;
+                }
+            }
+            let slack_is_basic = match relation {
+                Relation::Equal => false,
+                Relation::AtLeast => rhs < 0.0,
+                Relation::AtMost => rhs >= 0.0,
+            };
+            if slack_is_basic {
+                tableau.basis.push(next_slack - 1);
+            } else {
+                row[next_artificial] = 1.0;
+                tableau.basis.push(next_artificial);
+                next_artificial += 1;
+            }
+            tableau.rows.push(row);
+        }
+
+        // Phase one finds a feasible point by driving the artificial
+        // variables to zero.
+        let mut phase_one = vec![0.0; num_columns];
+        for cost in phase_one[first_artificial..].iter_mut() {
+            *cost = 1.0;
+        }
+        tableau.run(&phase_one, num_columns)?;
+        let infeasibility : f64 = tableau.rows.iter()
+            .zip(tableau.basis.iter())
+            .filter(|&(_, &column)| column >= first_artificial)
+            .map(|(row, _)| row[num_columns])
+            .sum();
+        let scale = self.constraints.iter().fold(1.0f64, |m, &(_, _, rhs)| m.max(rhs.abs()));
+        if infeasibility > 1e-7 * scale {
+            return Err(ProgramError::Infeasible);
+        }
+        // Artificial variables left in the basis are zero. Swap them for
+        // real columns where possible; rows where that is impossible are
+        // redundant and can stay as they are.
+        for row_index in 0..tableau.rows.len() {
+            if tableau.basis[row_index] >= first_artificial {
+                let column = (0..first_artificial).find(|&column| tableau.rows[row_index][column].abs() > EPSILON);
+                if let Some(column) = column {
+                    tableau.pivot(row_index, column);
+                }
+            }
+        }
+
+        // Phase two minimizes the real objective without letting the
+        // artificial variables back in.
+        let mut phase_two = vec![0.0; num_columns];
+        phase_two[..self.num_variables].copy_from_slice(objective);
+        tableau.run(&phase_two, first_artificial)?;
+
+        let mut solution = vec![0.0; self.num_variables];
+        for (row, &column) in tableau.rows.iter().zip(tableau.basis.iter()) {
+            if column < self.num_variables {
+                solution[column] = row[num_columns];
+            }
+        }
+        Ok(solution)
+    }
+}
+
+impl Tableau {
+    // Pivots until no column below `allowed_columns` improves the cost.
+    fn run(&mut self, costs: &[f64], allowed_columns: usize) -> Result<(), ProgramError> {
+        loop {
+            let entering = (0..allowed_columns).find(|&column| {
+                !self.basis.contains(&column) && self.reduced_cost(costs, column) < -EPSILON
+            });
+            let entering = match entering {
+                Some(column) => column,
+                None => return Ok(()),
+            };
+            let mut leaving : Option<(usize, f64)> = None;
+            for (row_index, row) in self.rows.iter().enumerate() {
+                if row[entering] > EPSILON {
+                    let ratio = row[self.num_columns] / row[entering];
+                    let better = match leaving {
+                        None => true,
+                        Some((best_row, best_ratio)) => {
+                            ratio < best_ratio - EPSILON
+                                || (ratio < best_ratio + EPSILON && self.basis[row_index] < self.basis[best_row])
+                        },
+                    };
+                    if better {
+                        leaving = Some((row_index, ratio));
+                    }
+                }
+            }
+            match leaving {
+                Some((row_index, _)) => self.pivot(row_index, entering),
+                None => return Err(ProgramError::Unbounded),
+            }
+        }
+    }
+
+    fn reduced_cost(&self, costs: &[f64], column: usize) -> f64 {
+        costs[column] - self.rows.iter()
+            .zip(self.basis.iter())
+            .map(|(row, &basic)| costs[basic] * row[column])
+            .sum::<f64>()
+    }
+
+    fn pivot(&mut self, row_index: usize, column: usize) {
+        let factor = self.rows[row_index][column];
+        for value in self.rows[row_index].iter_mut() {
+            *value /= factor;
+        }
+        let pivot_row = self.rows[row_index].clone();
+        for (other_index, row) in self.rows.iter_mut().enumerate() {
+            if other_index != row_index && row[column] != 0.0 {
+                let factor = row[column];
+                for (value, pivot_value) in row.iter_mut().zip(pivot_row.iter()) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+        self.basis[row_index] = column;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() <= 1e-9 * e.abs().max(1.0), "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn inequalities() {
+        // The cheapest way to get x + y of at least 4 with x at most 3.
+        let mut program = LinearProgram::new(2);
+        program.add_constraint(vec![1.0, 1.0], Relation::AtLeast, 4.0);
+        program.add_constraint(vec![1.0, 0.0], Relation::AtMost, 3.0);
+        assert_close(&program.minimize(&[1.0, 2.0]).unwrap(), &[3.0, 1.0]);
+    }
+
+    #[test]
+    fn equality_only() {
+        let mut program = LinearProgram::new(3);
+        program.add_constraint(vec![1.0, 1.0, 1.0], Relation::Equal, 6.0);
+        program.add_constraint(vec![1.0, -1.0, 0.0], Relation::Equal, 0.0);
+        program.add_constraint(vec![0.0, 0.0, 1.0], Relation::Equal, 2.0);
+        assert_close(&program.minimize(&[1.0, 1.0, 1.0]).unwrap(), &[2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn redundant_equalities() {
+        // The second equation repeats the first, which leaves an artificial
+        // variable in the basis after phase one.
+        let mut program = LinearProgram::new(2);
+        program.add_constraint(vec![1.0, 1.0], Relation::Equal, 2.0);
+        program.add_constraint(vec![2.0, 2.0], Relation::Equal, 4.0);
+        assert_close(&program.minimize(&[1.0, 3.0]).unwrap(), &[2.0, 0.0]);
+    }
+
+    #[test]
+    fn negative_right_hand_side() {
+        let mut program = LinearProgram::new(1);
+        program.add_constraint(vec![-1.0], Relation::AtMost, -5.0);
+        assert_close(&program.minimize(&[1.0]).unwrap(), &[5.0]);
+    }
+
+    #[test]
+    fn degenerate() {
+        // Several constraints meet at the optimum, where a ratio test ties
+        // at zero. Bland's rule must still finish.
+        let mut program = LinearProgram::new(4);
+        program.add_constraint(vec![0.5, -5.5, -2.5, 9.0], Relation::AtMost, 0.0);
+        program.add_constraint(vec![0.5, -1.5, -0.5, 1.0], Relation::AtMost, 0.0);
+        program.add_constraint(vec![1.0, 0.0, 0.0, 0.0], Relation::AtMost, 1.0);
+        assert_close(&program.minimize(&[-10.0, 57.0, 9.0, 24.0]).unwrap(), &[1.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn infeasible() {
+        let mut program = LinearProgram::new(2);
+        program.add_constraint(vec![1.0, 1.0], Relation::AtMost, 1.0);
+        program.add_constraint(vec![1.0, 1.0], Relation::AtLeast, 2.0);
+        assert_eq!(program.minimize(&[1.0, 1.0]).unwrap_err(), ProgramError::Infeasible);
+    }
+
+    #[test]
+    fn infeasible_sign() {
+        // Variables are never negative.
+        let mut program = LinearProgram::new(1);
+        program.add_constraint(vec![1.0], Relation::Equal, -1.0);
+        assert_eq!(program.minimize(&[0.0]).unwrap_err(), ProgramError::Infeasible);
+    }
+
+    #[test]
+    fn unbounded() {
+        let mut program = LinearProgram::new(2);
+        program.add_constraint(vec![1.0, -1.0], Relation::AtMost, 1.0);
+        assert_eq!(program.minimize(&[0.0, -1.0]).unwrap_err(), ProgramError::Unbounded);
+    }
+}
@@ -0,0 +1,259 @@
+// A system of linear equations, solved by Gaussian elimination. Rather than
+// just failing when there is no unique solution, solving reports which
+// variables are left free or which equations contradict each other.
+pub(crate) struct LinearSystem {
+    num_variables: usize,
+    equations: Vec<(Vec<f64>, f64)>,
+}
+
+pub(crate) enum SystemError {
+    // Each entry lists variables that can change together (with the given
+    // weights) without affecting any equation.
+    Underdetermined(Vec<Vec<(usize, f64)>>),
+    // The indices of a set of equations that cannot all hold at once.
+    Inconsistent(Vec<usize>),
+}
+
+// A row of the echelon form, along with the combination of original
+// equations it was built from.
+struct Row {
+    coefficients: Vec<f64>,
+    rhs: f64,
+    pivot: usize,
+    combination: Vec<f64>,
+}
+
+const EPSILON : f64 = 1e-9;
+
+impl LinearSystem {
+    pub(crate) fn new(num_variables: usize) -> LinearSystem {
+        LinearSystem {
+            num_variables,
+            equations: Vec::new(),
+        }
+    }
+
+    // Adds an equation and returns its index.
+    pub(crate) fn add_equation(&mut self, coefficients: Vec<f64>, rhs: f64) -> usize {
+        assert_eq!(coefficients.len(), self.num_variables);
+        self.equations.push((coefficients, rhs));
+        self.equations.len() - 1
+    }
+
+    pub(crate) fn solve(&self) -> Result<Vec<f64>, SystemError> {
+        let num_equations = self.equations.len();
+        let mut rows : Vec<Row> = Vec::new();
+        for (index, &(ref coefficients, rhs)) in self.equations.iter().enumerate() {
+            // Only the coefficients decide whether a pivot is zero, so that a
+            // large right hand side cannot hide a real pivot.
+            let scale = coefficients.iter().fold(0.0f64, |m, c| m.max(c.abs()));
+            let mut coefficients = coefficients.clone();
+            let mut rhs = rhs;
+            let mut combination = vec![0.0; num_equations];
+            combination[index] = 1.0;
+
+            // Eliminate the pivot of every earlier row. Each row is already
+            // zero at the pivots of the rows before it, so one pass suffices.
+            for row in rows.iter() {
+                let factor = coefficients[row.pivot] / row.coefficients[row.pivot];
+                if factor == 0.0 {
+                    continue;
+                }
+                for (c, r) in coefficients.iter_mut().zip(row.coefficients.iter()) {
+                    *c -= factor * r;
+                }
+                rhs -= factor * row.rhs;
+                for (c, r) in combination.iter_mut().zip(row.combination.iter()) {
+                    *c -= factor * r;
+                }
+            }
+
+            let pivot = (0..self.num_variables)
+                .max_by(|&a, &b| coefficients[a].abs().partial_cmp(&coefficients[b].abs()).unwrap());
+            match pivot {
+                Some(pivot) if coefficients[pivot].abs() > EPSILON * scale => {
+                    rows.push(Row {
+                        coefficients,
+                        rhs,
+                        pivot,
+                        combination,
+                    });
+                },
+                _ => {
+                    // The equation is a combination of earlier ones. That is
+                    // harmless unless it asks for a different value, judged
+                    // against the size of the right hand sides combined.
+                    let magnitude = combination.iter()
+                        .zip(self.equations.iter())
+                        .fold(scale.max(1.0), |m, (c, &(_, b))| m.max((c * b).abs()));
+                    if rhs.abs() > 1e-6 * magnitude {
+                        let conflicting = combination.iter()
+                            .enumerate()
+                            .filter(|&(_, c)| c.abs() > EPSILON)
+                            .map(|(i, _)| i)
+                            .collect();
+                        return Err(SystemError::Inconsistent(conflicting));
+                    }
+                },
+            }
+        }
+
+        let mut is_pivot = vec![false; self.num_variables];
+        for row in rows.iter() {
+            is_pivot[row.pivot] = true;
+        }
+        let free : Vec<usize> = (0..self.num_variables).filter(|&v| !is_pivot[v]).collect();
+        if !free.is_empty() {
+            let directions = free.iter()
+                .map(|&v| {
+                    let mut direction = vec![0.0; self.num_variables];
+                    direction[v] = 1.0;
+                    back_substitute(&rows, &mut direction, |_| 0.0);
+                    direction.into_iter()
+                        .enumerate()
+                        .filter(|&(_, x)| x.abs() > EPSILON)
+                        .collect()
+                })
+                .collect();
+            return Err(SystemError::Underdetermined(directions));
+        }
+
+        let mut solution = vec![0.0; self.num_variables];
+        back_substitute(&rows, &mut solution, |row| row.rhs);
+        Ok(solution)
+    }
+
+    // How much each equation with a non-zero right hand side adds to the
+    // value of `variable`. The solution is linear in the right hand sides, so
+    // these add up to the variable's value in the full solution.
+    pub(crate) fn contributions(&self, variable: usize) -> Vec<(usize, f64)> {
+        let mut contributions = Vec::new();
+        for (index, &(_, rhs)) in self.equations.iter().enumerate() {
+            if rhs == 0.0 {
+                continue;
+            }
+            let mut system = LinearSystem::new(self.num_variables);
+            for (other, &(ref coefficients, other_rhs)) in self.equations.iter().enumerate() {
+                system.add_equation(coefficients.clone(), if other == index { other_rhs } else { 0.0 });
+            }
+            if let Ok(solution) = system.solve() {
+                if solution[variable].abs() > EPSILON {
+                    contributions.push((index, solution[variable]));
+                }
+            }
+        }
+        contributions
+    }
+}
+
+// Fills in the pivot variables of `values` from the last row up, given
+// values for all of the free variables.
+fn back_substitute<F>(rows: &[Row], values: &mut [f64], rhs: F) where F: Fn(&Row) -> f64 {
+    for row in rows.iter().rev() {
+        let mut total = rhs(row);
+        for (v, &c) in row.coefficients.iter().enumerate() {
+            if v != row.pivot {
+                total -= c * values[v];
+            }
+        }
+        values[row.pivot] = total / row.coefficients[row.pivot];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() <= 1e-9 * e.abs().max(1.0), "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn unique() {
+        let mut system = LinearSystem::new(2);
+        system.add_equation(vec![1.0, 1.0], 3.0);
+        system.add_equation(vec![1.0, -1.0], 1.0);
+        match system.solve() {
+            Ok(solution) => assert_close(&solution, &[2.0, 1.0]),
+            Err(_) => panic!("expected a unique solution"),
+        }
+    }
+
+    #[test]
+    fn redundant_equations_are_harmless() {
+        let mut system = LinearSystem::new(2);
+        system.add_equation(vec![1.0, 1.0], 3.0);
+        system.add_equation(vec![2.0, 2.0], 6.0);
+        system.add_equation(vec![1.0, -1.0], 1.0);
+        match system.solve() {
+            Ok(solution) => assert_close(&solution, &[2.0, 1.0]),
+            Err(_) => panic!("expected a unique solution"),
+        }
+    }
+
+    #[test]
+    fn underdetermined() {
+        let mut system = LinearSystem::new(3);
+        system.add_equation(vec![1.0, -1.0, 0.0], 0.0);
+        system.add_equation(vec![0.0, 0.0, 1.0], 5.0);
+        match system.solve() {
+            Err(SystemError::Underdetermined(directions)) => {
+                assert_eq!(directions.len(), 1);
+                let variables : Vec<usize> = directions[0].iter().map(|&(variable, _)| variable).collect();
+                assert_eq!(variables, vec![0, 1]);
+            },
+            _ => panic!("expected an under-determined system"),
+        }
+    }
+
+    #[test]
+    fn inconsistent() {
+        let mut system = LinearSystem::new(2);
+        system.add_equation(vec![1.0, 0.0], 1.0);
+        system.add_equation(vec![0.0, 1.0], 2.0);
+        system.add_equation(vec![1.0, 1.0], 4.0);
+        match system.solve() {
+            Err(SystemError::Inconsistent(equations)) => assert_eq!(equations, vec![0, 1, 2]),
+            _ => panic!("expected an inconsistent system"),
+        }
+    }
+
+    #[test]
+    fn large_right_hand_side() {
+        // A target far larger than the coefficients must not make a pivot
+        // look like zero.
+        let mut system = LinearSystem::new(2);
+        system.add_equation(vec![1.0, -0.25], 0.0);
+        system.add_equation(vec![1.0, 0.0], 1e9);
+        match system.solve() {
+            Ok(solution) => assert_close(&solution, &[1e9, 4e9]),
+            Err(_) => panic!("expected a unique solution"),
+        }
+    }
+
+    #[test]
+    fn small_coefficients() {
+        let mut system = LinearSystem::new(2);
+        system.add_equation(vec![1e-6, 0.0], 1.0);
+        system.add_equation(vec![1e-6, -2e-6], 0.0);
+        match system.solve() {
+            Ok(solution) => assert_close(&solution, &[1e6, 5e5]),
+            Err(_) => panic!("expected a unique solution"),
+        }
+    }
+
+    #[test]
+    fn large_consistent_right_hand_sides() {
+        // Rounding in large right hand sides is not a contradiction.
+        let mut system = LinearSystem::new(1);
+        system.add_equation(vec![3.0], 1e12 / 3.0 * 3.0);
+        system.add_equation(vec![1.0], 1e12 / 3.0);
+        match system.solve() {
+            Ok(solution) => assert_close(&solution, &[1e12 / 3.0]),
+            Err(_) => panic!("expected a unique solution"),
+        }
+    }
+}
@@ -0,0 +1,207 @@
+extern crate factorio;
+
+use std::sync::Arc;
+
+use factorio::{Analysis, Database, Design};
+
+// The recipes the optimizer chooses for designs/oil_alternatives, after
+// making the given replacements in the design.
+fn optimize_oil_alternatives(replacements: &[(&str, &str)]) -> (Vec<&'static str>, Analysis) {
+    let mut contents = include_str!("../designs/oil_alternatives").to_string();
+    for &(from, to) in replacements.iter() {
+        assert!(contents.contains(from));
+        contents = contents.replace(from, to);
+    }
+    let design = Design::parse(contents.as_bytes()).unwrap();
+    let (chosen, analysis) = design.optimize().unwrap();
+    let mut recipes : Vec<&str> = chosen.buildings.iter().map(|building| building.recipe.name).collect();
+    recipes.sort();
+    (recipes, analysis)
+}
+
+// The default data without the expensive costs of Copper Cable.
+fn without_cable() -> Arc<Database> {
+    let base = include_str!("../data/base.txt");
+    let cable = "            Copper Cable: 2\n        Expensive\n";
+    assert!(base.contains(cable));
+    let without_cable = base.replace(cable, "            Copper Cable: 2\n");
+    Arc::new(Database::parse(without_cable.as_bytes()).unwrap())
+}
+
+#[test]
+fn oil_alternatives_picks_between_three_refinery_recipes() {
+    const ADVANCED : &str = "    Advanced Oil Processing\n";
+    const BASIC : &str = "    Basic Oil Processing\n";
+
+    // Advanced oil processing needs the fewest buildings.
+    let (recipes, analysis) = optimize_oil_alternatives(&[]);
+    assert_eq!(recipes, vec!["Advanced Oil Processing", "Heavy Oil Cracking", "Light Oil Cracking"]);
+    let crude = -analysis.line_rates["crude"];
+    let gas = analysis.line_rates["gas"];
+    assert!((gas - 100.0).abs() < 1e-3, "gas: {}", gas);
+    assert!((crude - 111.111).abs() < 1e-2, "crude: {}", crude);
+    assert!(analysis.line_rates["coal"].abs() < 1e-6);
+
+    // With too few refineries for it, basic oil processing makes up the rest.
+    let (recipes, _) = optimize_oil_alternatives(&[(ADVANCED, "    Advanced Oil Processing\n    Maximum Count: 2\n")]);
+    assert_eq!(recipes, vec!["Advanced Oil Processing", "Basic Oil Processing", "Heavy Oil Cracking", "Light Oil Cracking"]);
+
+    // Without either, the gas comes from coal.
+    let (recipes, analysis) = optimize_oil_alternatives(&[
+        (ADVANCED, "    Advanced Oil Processing\n    Maximum Count: 0\n"),
+        (BASIC, "    Basic Oil Processing\n    Maximum Count: 0\n"),
+    ]);
+    assert_eq!(recipes, vec!["Coal liquefaction", "Heavy Oil Cracking", "Light Oil Cracking"]);
+    assert!(analysis.line_rates["crude"].abs() < 1e-6);
+}
+
+#[test]
+fn designs_keep_the_database_they_were_read_with() {
+    // Coal that burns twice as long halves the coal burned.
+    let base = include_str!("../data/base.txt");
+    let rich_coal = base.replace("    Coal\n        Mining Hardness: 0.9\n        Fuel Value: 8000\n",
+                                 "    Coal\n        Mining Hardness: 0.9\n        Fuel Value: 16000\n");
+    assert_ne!(base, rich_coal);
+    let rich_coal = Arc::new(Database::parse(rich_coal.as_bytes()).unwrap());
+
+    let contents = b"Inputs\n    Coal: coal\n    Iron Ore: ore\nOutputs\n    Iron Plate: iron\nTargets\n    iron: 10\n\nStone Furnace\n    Iron Plate\n    Fuel\n        Coal: coal\n    Inputs\n        Iron Ore: ore\n    Outputs\n        Iron Plate: iron\n";
+    let normal = Design::parse(contents).unwrap();
+    let rich = Design::parse_with(&rich_coal, contents).unwrap();
+    // Each design keeps what its own data says, although the default data
+    // is the current one.
+    let normal_rate = normal.buildings[0].fuel_rate().unwrap();
+    let rich_rate = rich.buildings[0].fuel_rate().unwrap();
+    assert!((normal_rate - 2.0 * rich_rate).abs() < 1e-6, "{} and {}", normal_rate, rich_rate);
+    let normal_coal = -normal.analyze().unwrap().line_rates["coal"];
+    let rich_coal = -rich.analyze().unwrap().line_rates["coal"];
+    assert!((normal_coal - 2.0 * rich_coal).abs() < 1e-4, "{} and {}", normal_coal, rich_coal);
+}
+
+#[test]
+fn expensive_designs_warn_about_recipes_without_expensive_costs() {
+    // The default data gives expensive costs for every recipe.
+    let design = Design::from_file("designs/expensive_blue_circuits").unwrap();
+    assert_eq!(design.warnings, Vec::<String>::new());
+
+    let design = Design::from_file_with(&without_cable(), "designs/expensive_blue_circuits").unwrap();
+    assert_eq!(design.warnings, vec!["Copper Cable has no expensive costs in the game data, so its normal costs are used"]);
+}
+
+#[test]
+fn replaced_boiling_recipes_say_how_to_give_the_fuel() {
+    let contents = b"Inputs\n    Water: water\n    Coal: coal\nOutputs\n    Steam: steam\n\nBoiler\n    Boiling (Coal)\n    Inputs\n        Water: water\n        Coal: coal\n    Outputs\n        Steam: steam\n";
+    let error = Design::parse(contents).unwrap_err().to_string();
+    assert!(error.contains("Recipe Boiling (Coal) is now Boiling; list its Coal line under Fuel"), "{}", error);
+    // Recipe names are not case sensitive.
+    let contents = String::from_utf8_lossy(contents).replace("Boiling (Coal)", "boiling (coal)");
+    let error = Design::parse(contents.as_bytes()).unwrap_err().to_string();
+    assert!(error.contains("Recipe Boiling (Coal) is now Boiling; list its Coal line under Fuel"), "{}", error);
+}
+
+#[test]
+fn shared_beacons_are_powered_once() {
+    let shared = Design::from_file("designs/beaconed_green_circuits").unwrap();
+    let unshared = include_str!("../designs/beaconed_green_circuits").replace("        Shared By: 4\n", "");
+    let unshared = Design::parse(unshared.as_bytes()).unwrap();
+    let shared = shared.analyze().unwrap();
+    let unshared = unshared.analyze().unwrap();
+    for (shared, unshared) in shared.buildings.iter().zip(unshared.buildings.iter()) {
+        // Sharing changes the power of the beacons, not their effect.
+        assert!((shared.count - unshared.count).abs() < 1e-4);
+        assert!((4.0 * shared.beacon_energy - unshared.beacon_energy).abs() < 1e-2);
+    }
+    let contents = include_str!("../designs/beaconed_green_circuits").replace("Shared By: 4", "Shared By: 0.5");
+    let error = Design::parse(contents.as_bytes()).unwrap_err().to_string();
+    assert!(error.contains("Beacons are shared by at least 1 building"), "{}", error);
+}
+
+#[test]
+fn planned_designs_meet_their_targets() {
+    let design = Design::from_file("designs/planned_blue_circuits").unwrap();
+    assert!(!design.buildings.is_empty());
+    let analysis = design.analyze().unwrap();
+    let rate = analysis.line_rates["Processing Unit"];
+    assert!((rate - 1.0).abs() < 1e-3, "Processing Unit: {}", rate);
+
+    // A target naming a resource goes to the declared output line for it.
+    let contents = b"Inputs\n    Iron Plate: iron\n    Copper Plate: copper\nOutputs\n    Electronic Circuit: circuits\nTargets\n    Electronic Circuit: 10/s\n";
+    let design = Design::parse(contents).unwrap();
+    let analysis = design.analyze().unwrap();
+    let rate = analysis.line_rates["circuits"];
+    assert!((rate - 10.0).abs() < 1e-3, "circuits: {}", rate);
+    assert!(!analysis.line_rates.contains_key("Electronic Circuit"));
+}
+
+#[test]
+fn errors_point_at_the_design() {
+    let error_line = |contents: &str| {
+        let error = Design::parse(contents.as_bytes()).unwrap_err();
+        let location = error.location.expect(&error.message);
+        (error.message, location.line)
+    };
+    let furnace = "\nStone Furnace\n    Iron Plate\n    Fuel\n        Coal: coal\n    Inputs\n        Iron Ore: ore\n    Outputs\n        Iron Plate: iron\n";
+    let lines = "Inputs\n    Coal: coal\n    Iron Ore: ore\nOutputs\n    Iron Plate: iron\n";
+
+    let (message, line) = error_line(&format!("{}Overflow\n    Iron Plate: iron\n{}", lines, furnace));
+    assert_eq!((message.as_str(), line), ("Line iron cannot be both an overflow and an input or output", 7));
+
+    let (message, line) = error_line(&format!("{}{}    Bogus: 1\n", lines, furnace));
+    assert_eq!((message.as_str(), line), ("Unknown building property: Bogus", 15));
+
+    let (message, line) = error_line(&format!("{}Recipes\n    Iron Plate: Iron Plate\n{}", lines, furnace));
+    assert_eq!((message.as_str(), line), ("Recipes can only be chosen for designs without buildings", 6));
+
+    let allowed = furnace.replace("Stone Furnace", "Electric Furnace").replace("    Fuel\n        Coal: coal\n", "    Allowed Modules\n        Speed 3\n");
+    let (message, line) = error_line(&format!("{}{}", lines, allowed));
+    assert_eq!((message.as_str(), line), ("Allowed Modules are only used when the design has an Optimize objective", 9));
+
+    let (message, line) = error_line(&format!("{}    Electricity: power\n    Electricity: grid\n", lines));
+    assert_eq!((message.as_str(), line), ("Only one power grid is supported, found Electricity lines power and grid", 7));
+}
+
+#[test]
+fn yields_must_be_positive() {
+    let design = include_str!("../designs/mined_smelting");
+    for &(yield_text, message) in [("0%", "Yields are more than 0%"), ("-20%", "Yields are more than 0%"), ("lots", "Invalid yield")].iter() {
+        let contents = design.replace("Yield: 150%", &format!("Yield: {}", yield_text));
+        let error = Design::parse(contents.as_bytes()).unwrap_err();
+        assert_eq!(error.message, message);
+        assert_eq!(error.location.unwrap().text, yield_text);
+    }
+}
+
+#[test]
+fn includes_carry_pipe_lengths_and_warnings() {
+    let dir = std::env::temp_dir().join(format!("factorio-include-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let child = "Recipe Mode: Expensive\nInputs\n    Copper Plate: copper\nOutputs\n    Crude Oil: crude_out\n    Copper Cable: cable\nPipe Lengths\n    crude_out: 40\n\nPumpjack\n    Crude Oil\n    Outputs\n        Crude Oil: crude_out\n\nAssembling Machine 2\n    Copper Cable\n    Inputs\n        Copper Plate: copper\n    Outputs\n        Copper Cable: cable\n";
+    std::fs::write(dir.join("child"), child).unwrap();
+    let parent = "Recipe Mode: Expensive\nOutputs\n    Crude Oil: crude\n\nInclude\n    File: child\n    Lines\n        crude_out: crude\n";
+    let read = |contents: &str| {
+        std::fs::write(dir.join("parent"), contents).unwrap();
+        Design::from_file_with(&without_cable(), dir.join("parent"))
+    };
+
+    let design = read(parent).unwrap();
+    assert_eq!(design.pipe_lengths["crude"], 40);
+    // The included cable assembler is warned about once.
+    assert_eq!(design.warnings, vec!["Copper Cable has no expensive costs in the game data, so its normal costs are used"]);
+
+    let design = read(&format!("{}Pipe Lengths\n    crude: 100\n", parent)).unwrap();
+    assert_eq!(design.pipe_lengths["crude"], 100);
+
+    let error = read(&parent.replace("Recipe Mode: Expensive\n", "")).unwrap_err();
+    assert_eq!(error.message, "child has Recipe Mode Expensive, but the including design has Recipe Mode Normal");
+    assert_eq!(error.location.unwrap().line, 4);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn modules_must_fit_in_the_building() {
+    let design = include_str!("../designs/advanced_circuits");
+    assert!(Design::parse(design.as_bytes()).is_ok());
+    let contents = design.replacen("Productivity 1: 2", "Productivity 1: 3", 1);
+    let error = Design::parse(contents.as_bytes()).unwrap_err();
+    assert_eq!(error.message, "Assembling Machine 2 has 2 module slots, but 3 modules were given");
+    assert_eq!(error.location.unwrap().text, "Modules");
+}
@@ -0,0 +1,152 @@
+extern crate factorio;
+
+use factorio::{Database, EnergySource};
+
+// A small data.raw dump with a recipe that has difficulty variants, burner
+// and electric machines, a resource field that needs a fluid to mine, and a
+// fuel that nothing makes.
+static RAW : &str = r#"{
+    "item": {
+        "coal": {"stack_size": 50, "fuel_value": "4MJ"},
+        "wood": {"stack_size": 100, "fuel_value": "2MJ"},
+        "iron-plate": {"stack_size": 100},
+        "iron-gear-wheel": {"stack_size": 100},
+        "iron-ore": {"stack_size": 50},
+        "uranium-ore": {"stack_size": 50}
+    },
+    "fluid": {
+        "sulfuric-acid": {}
+    },
+    "assembling-machine": {
+        "assembling-machine-2": {
+            "energy_usage": "150kW",
+            "crafting_speed": 0.75,
+            "crafting_categories": ["crafting"],
+            "energy_source": {"type": "electric"},
+            "module_specification": {"module_slots": 2}
+        }
+    },
+    "furnace": {
+        "stone-furnace": {
+            "energy_usage": "90kW",
+            "crafting_speed": 1,
+            "crafting_categories": ["smelting"],
+            "energy_source": {"type": "burner", "effectivity": 1}
+        }
+    },
+    "mining-drill": {
+        "electric-mining-drill": {
+            "energy_usage": "90kW",
+            "mining_speed": 0.5,
+            "mining_power": 3,
+            "resource_categories": ["basic-solid"],
+            "energy_source": {"type": "electric"}
+        }
+    },
+    "resource": {
+        "uranium-ore": {
+            "minable": {
+                "hardness": 0.9,
+                "mining_time": 4,
+                "result": "uranium-ore",
+                "required_fluid": "sulfuric-acid",
+                "fluid_amount": 10
+            }
+        }
+    },
+    "recipe": {
+        "iron-plate": {
+            "category": "smelting",
+            "energy_required": 3.5,
+            "ingredients": [["iron-ore", 1]],
+            "result": "iron-plate"
+        },
+        "iron-gear-wheel": {
+            "normal": {"ingredients": [["iron-plate", 2]], "result": "iron-gear-wheel"},
+            "expensive": {"ingredients": [["iron-plate", 4]], "result": "iron-gear-wheel"}
+        }
+    }
+}"#;
+
+fn import() -> Database {
+    Database::from_data_raw(RAW.as_bytes()).unwrap()
+}
+
+#[test]
+fn recipes_keep_both_variants() {
+    let database = import();
+    let gear = database.find_recipe("iron-gear-wheel").unwrap();
+    assert_eq!(gear.name, "Iron Gear Wheel");
+    assert_eq!(gear.time, 0.5);
+    assert_eq!(gear.inputs.iter().map(|&(resource, amount)| (resource.name(), amount)).collect::<Vec<_>>(),
+               vec![("Iron Plate", 2.0)]);
+    let expensive = gear.expensive.as_ref().unwrap();
+    assert_eq!(expensive.inputs.iter().map(|&(resource, amount)| (resource.name(), amount)).collect::<Vec<_>>(),
+               vec![("Iron Plate", 4.0)]);
+    // Recipes without variants cost the same in both modes.
+    let plate = database.find_recipe("Iron Plate").unwrap();
+    assert_eq!(plate.time, 3.5);
+    let expensive = plate.expensive.as_ref().unwrap();
+    assert_eq!((&expensive.inputs, &expensive.outputs, expensive.time), (&plate.inputs, &plate.outputs, plate.time));
+}
+
+#[test]
+fn burner_and_electric_machines() {
+    let database = import();
+    let assembler = database.find_building("Assembling Machine 2").unwrap();
+    assert_eq!(assembler.energy_source, EnergySource::Electric);
+    assert_eq!(assembler.crafting_speed, 0.75);
+    assert_eq!(assembler.module_slots, 2);
+    // Crafting machines without a drain get the default one.
+    assert!((assembler.drain - 5.0).abs() < 1e-4);
+
+    let furnace = database.find_building("Stone Furnace").unwrap();
+    assert_eq!(furnace.energy_source, EnergySource::Burner { efficiency: 1.0 });
+    assert_eq!(furnace.drain, 0.0);
+
+    let drill = database.find_building("Electric Mining Drill").unwrap();
+    assert_eq!(drill.mining_power, 3.0);
+    assert_eq!(drill.drain, 0.0);
+}
+
+#[test]
+fn mining_with_a_required_fluid() {
+    let database = import();
+    let mining = database.find_recipe("Uranium Ore").unwrap();
+    assert!(mining.category.mining);
+    assert_eq!(mining.time, 4.0);
+    assert_eq!(mining.inputs.len(), 1);
+    let (fluid, amount) = mining.inputs[0];
+    assert_eq!(fluid.name(), "Sulfuric Acid");
+    assert!(fluid.is_fluid());
+    assert_eq!(amount, 1.0);
+    let (ore, _) = mining.outputs[0];
+    assert_eq!(ore.mining_hardness(), 0.9);
+}
+
+#[test]
+fn fuel_values() {
+    let database = import();
+    let coal = database.find_resource("coal").unwrap();
+    assert_eq!(coal.fuel_value(), Some(4000.0));
+    // Fuels are imported even when no recipe uses them.
+    assert_eq!(database.find_resource("wood").and_then(|wood| wood.fuel_value()), Some(2000.0));
+    assert_eq!(database.find_resource("iron-plate").and_then(|plate| plate.fuel_value()), None);
+    assert_eq!(database.default_fuel(), Some(coal));
+}
+
+#[test]
+fn duplicate_names_are_rejected() {
+    // Both ids are named Iron Plate.
+    let raw = RAW.replace(r#""iron-gear-wheel": {
+            "normal""#, r#""iron-Plate": {
+            "category": "smelting",
+            "ingredients": [["iron-ore", 1]],
+            "result": "iron-plate"
+        },
+        "iron-gear-wheel": {
+            "normal""#);
+    assert_ne!(raw, RAW);
+    let error = Database::from_data_raw(raw.as_bytes()).unwrap_err().to_string();
+    assert!(error.contains("Duplicate recipe: Iron Plate"), "{}", error);
+}